@@ -1,4 +1,8 @@
-use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+// The builders below index on purpose: the codegen they are compared on is
+// that of the plain `0..N` loop.
+#![allow(clippy::needless_range_loop)]
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group};
 use std::mem::MaybeUninit;
 use std::ptr;
 
@@ -39,6 +43,24 @@ fn consume_sum<const N: usize>(a: &[u8; N]) -> u64 {
     black_box(s)
 }
 
+// Out-of-line copies of the two build bodies timed below, so the codegen of
+// an inlined builder has a symbol of its own: `run-suite asm array --check`
+// looks for the copy here. They are never timed.
+#[inline(never)]
+fn run_build_return<const N: usize>() -> u64 {
+    let out = build_return_inline::<N>(black_box(123));
+    consume_sum::<N>(black_box(&out))
+}
+
+#[inline(never)]
+fn run_build_outparam<const N: usize>() -> u64 {
+    let mut out = MaybeUninit::<[u8; N]>::uninit();
+    let out_ref: &mut [u8; N] = unsafe { &mut *out.as_mut_ptr() };
+    build_outparam_inline::<N>(black_box(123), out_ref);
+    let borrow: &[u8; N] = &*out_ref;
+    consume_sum::<N>(black_box(borrow))
+}
+
 fn make_src<const N: usize>() -> [u8; N] {
     let mut a = [0u8; N];
     let seed = black_box(123u8);
@@ -94,6 +116,9 @@ fn bench_size<const N: usize>(c: &mut Criterion) {
     });
 
     g.finish();
+
+    // Emits the asm-inspection copies for this N without timing them.
+    black_box::<[fn() -> u64; 2]>([run_build_return::<N>, run_build_outparam::<N>]);
 }
 
 fn benches(c: &mut Criterion) {
@@ -110,4 +135,4 @@ fn benches(c: &mut Criterion) {
 }
 
 criterion_group!(benches_group, benches);
-criterion_main!(benches_group);
+rust_examples::bench_main!(benches_group);
@@ -0,0 +1,217 @@
+use criterion::measurement::Measurement;
+use criterion::{Criterion, black_box, criterion_group};
+use rust_examples::executor::{block_on, yield_now};
+use rust_examples::perf;
+use std::future::Future;
+use std::pin::Pin;
+
+// Calls per measured iteration; all of them run inside one `block_on`.
+const CALLS: u64 = 100_000;
+
+// ==== Repositories ====
+
+// Native `async fn` in a trait: each impl returns its own anonymous future
+// type, so the trait is not dyn-compatible and callers must be generic.
+trait AsyncUserRepo {
+    async fn get_user(&self, id: u64) -> u64;
+}
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+// The dyn-compatible shape: every call allocates its future on the heap.
+trait DynUserRepo {
+    fn get_user(&self, id: u64) -> BoxFuture<'_, u64>;
+}
+
+// `io` makes every lookup return `Pending` once before completing, so the
+// executor goes through a wake-up per call. The benches alternate between
+// the two repos, so every dispatch site sees more than one implementation.
+struct MemoryRepo {
+    io: bool,
+}
+
+struct CachedRepo {
+    io: bool,
+    hot: u64,
+}
+
+async fn lookup(io: bool, value: u64) -> u64 {
+    if io {
+        yield_now().await;
+    }
+    value
+}
+
+impl MemoryRepo {
+    fn get_user_sync(&self, id: u64) -> u64 {
+        id ^ 42
+    }
+}
+
+impl CachedRepo {
+    fn get_user_sync(&self, id: u64) -> u64 {
+        id.min(self.hot)
+    }
+}
+
+impl AsyncUserRepo for MemoryRepo {
+    async fn get_user(&self, id: u64) -> u64 {
+        lookup(self.io, id ^ 42).await
+    }
+}
+
+impl AsyncUserRepo for CachedRepo {
+    async fn get_user(&self, id: u64) -> u64 {
+        lookup(self.io, id.min(self.hot)).await
+    }
+}
+
+impl DynUserRepo for MemoryRepo {
+    fn get_user(&self, id: u64) -> BoxFuture<'_, u64> {
+        Box::pin(lookup(self.io, id ^ 42))
+    }
+}
+
+impl DynUserRepo for CachedRepo {
+    fn get_user(&self, id: u64) -> BoxFuture<'_, u64> {
+        Box::pin(lookup(self.io, id.min(self.hot)))
+    }
+}
+
+// Hand-written enum dispatch: a closed set of repos, one `match` per call,
+// and a single future type that holds whichever variant's future is live.
+enum AnyRepo {
+    Memory(MemoryRepo),
+    Cached(CachedRepo),
+}
+
+impl AnyRepo {
+    async fn get_user(&self, id: u64) -> u64 {
+        match self {
+            AnyRepo::Memory(r) => AsyncUserRepo::get_user(r, id).await,
+            AnyRepo::Cached(r) => AsyncUserRepo::get_user(r, id).await,
+        }
+    }
+}
+
+// ==== Use cases ====
+
+fn use_case_sync(memory: &MemoryRepo, cached: &CachedRepo, id: u64) -> u64 {
+    if id.is_multiple_of(2) {
+        memory.get_user_sync(id)
+    } else {
+        cached.get_user_sync(id)
+    }
+}
+
+async fn use_case_generic<R: AsyncUserRepo>(repo: &R, id: u64) -> u64 {
+    repo.get_user(id).await
+}
+
+// The static counterpart of picking a repo at run time: one instantiation
+// per repo type, selected by a branch.
+async fn use_case_generic_either(memory: &MemoryRepo, cached: &CachedRepo, id: u64) -> u64 {
+    if id.is_multiple_of(2) {
+        use_case_generic(memory, id).await
+    } else {
+        use_case_generic(cached, id).await
+    }
+}
+
+async fn use_case_dyn(repo: &dyn DynUserRepo, id: u64) -> u64 {
+    repo.get_user(id).await
+}
+
+async fn use_case_enum(repo: &AnyRepo, id: u64) -> u64 {
+    repo.get_user(id).await
+}
+
+// ==== Benchmarks ====
+
+fn bench_async_dispatch<M: Measurement + 'static>(c: &mut Criterion<M>, prefix: &str) {
+    for (mode, io) in [("ready", false), ("yield", true)] {
+        let mut group = c.benchmark_group(format!("{prefix}async_{mode}"));
+        let memory = MemoryRepo { io };
+        let cached = CachedRepo { io, hot: CALLS / 2 };
+        // Hidden from the optimiser so the vtable call and the `match`
+        // cannot be resolved at compile time.
+        let repos_dyn: [&dyn DynUserRepo; 2] = black_box([&memory, &cached]);
+        let repos_enum = black_box([
+            AnyRepo::Memory(MemoryRepo { io }),
+            AnyRepo::Cached(CachedRepo { io, hot: CALLS / 2 }),
+        ]);
+
+        // The synchronous call from `trait_direct`, for scale. Only
+        // meaningful when nothing is pending.
+        if !io {
+            group.bench_function("sync_concrete_call", |b| {
+                b.iter(|| {
+                    block_on(async {
+                        let mut sum = 0u64;
+                        for i in 0..CALLS {
+                            sum = sum.wrapping_add(black_box(use_case_sync(&memory, &cached, i)));
+                        }
+                        sum
+                    })
+                })
+            });
+        }
+
+        group.bench_function("native_generic", |b| {
+            b.iter(|| {
+                block_on(async {
+                    let mut sum = 0u64;
+                    for i in 0..CALLS {
+                        sum = sum.wrapping_add(black_box(
+                            use_case_generic_either(&memory, &cached, i).await,
+                        ));
+                    }
+                    sum
+                })
+            })
+        });
+
+        group.bench_function("boxed_dyn", |b| {
+            b.iter(|| {
+                block_on(async {
+                    let mut sum = 0u64;
+                    for i in 0..CALLS {
+                        sum = sum.wrapping_add(black_box(
+                            use_case_dyn(repos_dyn[(i % 2) as usize], i).await,
+                        ));
+                    }
+                    sum
+                })
+            })
+        });
+
+        group.bench_function("enum_dispatch", |b| {
+            b.iter(|| {
+                block_on(async {
+                    let mut sum = 0u64;
+                    for i in 0..CALLS {
+                        sum = sum.wrapping_add(black_box(
+                            use_case_enum(&repos_enum[(i % 2) as usize], i).await,
+                        ));
+                    }
+                    sum
+                })
+            })
+        });
+
+        group.finish();
+    }
+}
+
+fn bench_async_wall_time(c: &mut Criterion) {
+    bench_async_dispatch(c, "");
+}
+
+// Same as in `trait_direct`: the differences are a few instructions and
+// one allocation per call, so repeat them under a CPU counter.
+fn counters() {
+    perf::run_counted(|c, label| bench_async_dispatch(c, &format!("{label}/")));
+}
+
+criterion_group!(benches, bench_async_wall_time);
+rust_examples::bench_main!(benches, counters);
@@ -0,0 +1,219 @@
+use bumpalo::Bump;
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, Criterion, Throughput, black_box, criterion_group};
+use rust_examples::alloc;
+use rust_examples::data::Gen;
+use std::cell::RefCell;
+
+// Same shape as `vectors_fight_2`: REPS short-lived buffers of up to K
+// elements per iteration. The question here is where each buffer comes from.
+const REPS: usize = 100_000;
+const K: usize = 8;
+// The reuse strategies may allocate their one buffer (or pool, or arena)
+// once per iteration, but never per repetition.
+const MAX_REUSE_ALLOCS: u64 = 2;
+// Elements kept at the front of the buffer by the truncate variant.
+const HEADER: usize = 2;
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
+
+fn fill(v: &mut Vec<u32>, sz: usize) {
+    for i in 0..(sz as u32) {
+        v.push(black_box(i));
+    }
+}
+
+fn do_work_on(v: &[u32]) -> u64 {
+    v.iter().fold(0u64, |acc, &x| acc.wrapping_add(x as u64))
+}
+
+// -----------------------------
+// Strategies
+// -----------------------------
+
+// Baseline: a new Vec per repetition, freed at the end of it.
+fn fresh_vec(sizes: &[usize]) -> u64 {
+    let mut acc = 0u64;
+    for &sz in sizes {
+        let mut v = Vec::with_capacity(K);
+        fill(&mut v, sz);
+        acc = acc.wrapping_add(do_work_on(&v));
+    }
+    acc
+}
+
+// One buffer for the whole loop; `clear` keeps the capacity.
+fn clear_reuse(sizes: &[usize]) -> u64 {
+    let mut acc = 0u64;
+    let mut v = Vec::with_capacity(K);
+    for &sz in sizes {
+        v.clear();
+        fill(&mut v, sz);
+        acc = acc.wrapping_add(do_work_on(&v));
+    }
+    acc
+}
+
+// Like `clear`, but a fixed prefix survives each repetition.
+fn truncate_reuse(sizes: &[usize]) -> u64 {
+    let mut acc = 0u64;
+    let mut v = Vec::with_capacity(HEADER + K);
+    fill(&mut v, HEADER);
+    for &sz in sizes {
+        v.truncate(HEADER);
+        fill(&mut v, sz);
+        acc = acc.wrapping_add(do_work_on(&v[HEADER..]));
+    }
+    acc
+}
+
+// Consumes the elements by value, leaving the buffer empty but allocated.
+fn drain_reuse(sizes: &[usize]) -> u64 {
+    let mut acc = 0u64;
+    let mut v = Vec::with_capacity(K);
+    for &sz in sizes {
+        fill(&mut v, sz);
+        for x in v.drain(..) {
+            acc = acc.wrapping_add(x as u64);
+        }
+    }
+    acc
+}
+
+/// A free list of cleared buffers: `take` pops one (or makes one when the
+/// list is empty), `give` clears it and pushes it back.
+struct Pool {
+    free: Vec<Vec<u32>>,
+}
+
+impl Pool {
+    fn new() -> Pool {
+        Pool { free: Vec::new() }
+    }
+
+    fn take(&mut self) -> Vec<u32> {
+        self.free.pop().unwrap_or_else(|| Vec::with_capacity(K))
+    }
+
+    fn give(&mut self, mut v: Vec<u32>) {
+        v.clear();
+        self.free.push(v);
+    }
+}
+
+fn pool(sizes: &[usize]) -> u64 {
+    let mut acc = 0u64;
+    let mut pool = Pool::new();
+    for &sz in sizes {
+        let mut v = pool.take();
+        fill(&mut v, sz);
+        acc = acc.wrapping_add(do_work_on(&v));
+        pool.give(v);
+    }
+    acc
+}
+
+thread_local! {
+    static SCRATCH: RefCell<Vec<u32>> = RefCell::new(Vec::with_capacity(K));
+}
+
+// A per-thread scratch buffer, for code that cannot thread one through.
+fn thread_local(sizes: &[usize]) -> u64 {
+    let mut acc = 0u64;
+    for &sz in sizes {
+        SCRATCH.with_borrow_mut(|v| {
+            v.clear();
+            fill(v, sz);
+            acc = acc.wrapping_add(do_work_on(v));
+        });
+    }
+    acc
+}
+
+// One arena per iteration, like a per-request arena: every buffer is a
+// pointer bump and nothing is freed until the arena drops.
+fn bump_arena(sizes: &[usize]) -> u64 {
+    let mut acc = 0u64;
+    let bump = Bump::with_capacity(REPS * K * size_of::<u32>());
+    for &sz in sizes {
+        let mut v = bumpalo::collections::Vec::with_capacity_in(K, &bump);
+        for i in 0..(sz as u32) {
+            v.push(black_box(i));
+        }
+        acc = acc.wrapping_add(do_work_on(&v));
+    }
+    acc
+}
+
+// -----------------------------
+// Benches
+// -----------------------------
+
+type Variant = (&'static str, fn(&[usize]) -> u64);
+
+const VARIANTS: [Variant; 7] = [
+    ("fresh_vec", fresh_vec),
+    ("clear_reuse", clear_reuse),
+    ("truncate_reuse", truncate_reuse),
+    ("drain_reuse", drain_reuse),
+    ("pool", pool),
+    ("thread_local", thread_local),
+    ("bump_arena", bump_arena),
+];
+
+// Every strategy must compute the same result, and all but the baseline
+// must keep allocations off the per-repetition path. Only checked in a
+// `count-allocs` build (see `rust_examples::alloc`).
+fn check_variants(group: &str, sizes: &[usize]) {
+    if !alloc::ENABLED {
+        return;
+    }
+    let expected = fresh_vec(sizes);
+    for &(name, f) in &VARIANTS {
+        let stats = alloc::report(&format!("{group}/{name}"), || {
+            assert_eq!(
+                f(sizes),
+                expected,
+                "{group}/{name} computed a different sum"
+            );
+        });
+        if name != "fresh_vec" {
+            assert!(
+                stats.allocations <= MAX_REUSE_ALLOCS,
+                "{group}/{name} allocated {} times",
+                stats.allocations
+            );
+        }
+    }
+}
+
+fn bench_variants(g: &mut BenchmarkGroup<'_, WallTime>, sizes: &[usize]) {
+    for &(name, f) in &VARIANTS {
+        g.bench_function(name, |b| b.iter(|| black_box(f(sizes))));
+    }
+}
+
+fn bench_buffer_reuse(c: &mut Criterion) {
+    // Varied sizes come from the same stream as `vectors_fight_2`'s.
+    let cases = [
+        ("fixed", vec![K; REPS]),
+        (
+            "varied",
+            Gen::new("vectors_fight_2/sizes").uniform(REPS, 0..=K),
+        ),
+    ];
+    for (case, sizes) in &cases {
+        let group = format!("buffer_reuse_{case}");
+        check_variants(&group, sizes);
+
+        let mut g = c.benchmark_group(group);
+        g.throughput(Throughput::Elements(sizes.iter().sum::<usize>() as u64));
+        bench_variants(&mut g, sizes);
+        g.finish();
+    }
+}
+
+criterion_group!(benches, bench_buffer_reuse);
+rust_examples::bench_main!(benches);
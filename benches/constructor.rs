@@ -1,82 +1,9 @@
-use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use criterion::{Criterion, black_box, criterion_group};
+use rust_examples::fixtures::{Large, Medium, Small};
 use std::collections::HashMap;
 
-// Define the structures
-#[derive(Clone)]
-struct Small {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<u8>,
-    e: HashMap<String, i32>,
-    f: bool,
-    g: Option<i32>,
-    h: Vec<String>,
-    i: f32,
-    j: HashMap<String, Vec<i32>>,
-}
-
-#[derive(Clone)]
-struct Medium {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<u8>,
-    e: HashMap<String, i32>,
-    f: i64,
-    g: Vec<i32>,
-    h: String,
-    i: HashMap<i32, String>,
-    j: f32,
-    k: Option<i32>,
-    l: Vec<f64>,
-    m: bool,
-    n: Vec<String>,
-    o: HashMap<i64, f64>,
-    p: String,
-    q: Option<Vec<i32>>,
-    r: Vec<u32>,
-    s: HashMap<String, Vec<u8>>,
-    t: f64,
-}
-
-#[derive(Clone)]
-struct Large {
-    a: i64,
-    b: f32,
-    c: String,
-    d: Vec<u32>,
-    e: HashMap<String, Vec<u8>>,
-    f: Option<u64>,
-    g: i64,
-    h: Vec<i32>,
-    i: HashMap<i32, String>,
-    j: f64,
-    k: u32,
-    l: Vec<f64>,
-    m: String,
-    n: HashMap<i64, i32>,
-    o: Vec<String>,
-    p: Vec<i64>,
-    q: Vec<u8>,
-    r: HashMap<String, String>,
-    s: Vec<u32>,
-    t: Option<Vec<i32>>,
-    u: f64,
-    v: String,
-    w: i32,
-    x: f32,
-    y: i64,
-    z: String,
-    aa: Vec<f32>,
-    ab: u64,
-    ac: HashMap<String, Vec<i32>>,
-    ad: Option<String>,
-    ae: Vec<String>,
-    af: HashMap<i64, f64>,
-}
-
 // Constructors by move accepting all arguments
+#[allow(clippy::too_many_arguments)]
 fn construct_small_move(
     a: i32,
     b: String,
@@ -103,6 +30,7 @@ fn construct_small_move(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn construct_medium_move(
     a: i32,
     b: String,
@@ -149,6 +77,7 @@ fn construct_medium_move(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn construct_large_move(
     a: i64,
     b: f32,
@@ -220,6 +149,7 @@ fn construct_large_move(
 }
 
 // Boxed constructors
+#[allow(clippy::too_many_arguments)]
 fn construct_small_boxed(
     a: i32,
     b: String,
@@ -246,6 +176,7 @@ fn construct_small_boxed(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn construct_medium_boxed(
     a: i32,
     b: String,
@@ -292,6 +223,7 @@ fn construct_medium_boxed(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn construct_large_boxed(
     a: i64,
     b: f32,
@@ -369,7 +301,7 @@ fn benchmark_constructors(c: &mut Criterion) {
             let _ = black_box(Small {
                 a: 1,
                 b: "text".into(),
-                c: 3.14,
+                c: 3.5,
                 d: vec![1, 2, 3],
                 e: HashMap::new(),
                 f: true,
@@ -386,7 +318,7 @@ fn benchmark_constructors(c: &mut Criterion) {
             let _ = black_box(construct_small_move(
                 1,
                 "text".into(),
-                3.14,
+                3.5,
                 vec![1, 2, 3],
                 HashMap::new(),
                 true,
@@ -403,7 +335,7 @@ fn benchmark_constructors(c: &mut Criterion) {
             let _ = black_box(construct_small_boxed(
                 1,
                 "text".into(),
-                3.14,
+                3.5,
                 vec![1, 2, 3],
                 HashMap::new(),
                 true,
@@ -421,7 +353,7 @@ fn benchmark_constructors(c: &mut Criterion) {
             let _ = black_box(Medium {
                 a: 1,
                 b: "text".into(),
-                c: 3.14,
+                c: 3.5,
                 d: vec![1, 2, 3],
                 e: HashMap::new(),
                 f: 64,
@@ -438,7 +370,7 @@ fn benchmark_constructors(c: &mut Criterion) {
                 q: Some(vec![1, 2, 3]),
                 r: vec![1, 2],
                 s: HashMap::new(),
-                t: 3.14,
+                t: 3.5,
             });
         })
     });
@@ -448,7 +380,7 @@ fn benchmark_constructors(c: &mut Criterion) {
             let _ = black_box(construct_medium_move(
                 1,
                 "text".into(),
-                3.14,
+                3.5,
                 vec![1, 2, 3],
                 HashMap::new(),
                 64,
@@ -465,7 +397,7 @@ fn benchmark_constructors(c: &mut Criterion) {
                 Some(vec![1, 2, 3]),
                 vec![1, 2],
                 HashMap::new(),
-                3.14,
+                3.5,
             ));
         })
     });
@@ -475,7 +407,7 @@ fn benchmark_constructors(c: &mut Criterion) {
             let _ = black_box(construct_medium_boxed(
                 1,
                 "text".into(),
-                3.14,
+                3.5,
                 vec![1, 2, 3],
                 HashMap::new(),
                 64,
@@ -492,7 +424,7 @@ fn benchmark_constructors(c: &mut Criterion) {
                 Some(vec![1, 2, 3]),
                 vec![1, 2],
                 HashMap::new(),
-                3.14,
+                3.5,
             ));
         })
     });
@@ -502,7 +434,7 @@ fn benchmark_constructors(c: &mut Criterion) {
         b.iter(|| {
             let _ = black_box(Large {
                 a: 1000,
-                b: 3.14,
+                b: 3.5,
                 c: "Large".to_string(),
                 d: vec![1, 2, 3],
                 e: HashMap::new(),
@@ -510,7 +442,7 @@ fn benchmark_constructors(c: &mut Criterion) {
                 g: 12345,
                 h: vec![10, 20, 30],
                 i: HashMap::new(),
-                j: 6.28,
+                j: 6.5,
                 k: 99,
                 l: vec![1.1, 2.2, 3.3],
                 m: "Hello".to_string(),
@@ -524,7 +456,7 @@ fn benchmark_constructors(c: &mut Criterion) {
                 u: 9.81,
                 v: "world".to_string(),
                 w: 42,
-                x: 2.71,
+                x: 2.5,
                 y: 98765,
                 z: "end".to_string(),
                 aa: vec![1.0, 2.0, 3.0],
@@ -541,7 +473,7 @@ fn benchmark_constructors(c: &mut Criterion) {
         b.iter(|| {
             let _ = black_box(construct_large_move(
                 1000,
-                3.14,
+                3.5,
                 "Large".to_string(),
                 vec![1, 2, 3],
                 HashMap::new(),
@@ -549,7 +481,7 @@ fn benchmark_constructors(c: &mut Criterion) {
                 12345,
                 vec![10, 20, 30],
                 HashMap::new(),
-                6.28,
+                6.5,
                 99,
                 vec![1.1, 2.2, 3.3],
                 "Hello".to_string(),
@@ -563,7 +495,7 @@ fn benchmark_constructors(c: &mut Criterion) {
                 9.81,
                 "world".to_string(),
                 42,
-                2.71,
+                2.5,
                 98765,
                 "end".to_string(),
                 vec![1.0, 2.0, 3.0],
@@ -580,7 +512,7 @@ fn benchmark_constructors(c: &mut Criterion) {
         b.iter(|| {
             let _ = black_box(construct_large_boxed(
                 1000,
-                3.14,
+                3.5,
                 "Large".to_string(),
                 vec![1, 2, 3],
                 HashMap::new(),
@@ -588,7 +520,7 @@ fn benchmark_constructors(c: &mut Criterion) {
                 12345,
                 vec![10, 20, 30],
                 HashMap::new(),
-                6.28,
+                6.5,
                 99,
                 vec![1.1, 2.2, 3.3],
                 "Hello".to_string(),
@@ -602,7 +534,7 @@ fn benchmark_constructors(c: &mut Criterion) {
                 9.81,
                 "world".to_string(),
                 42,
-                2.71,
+                2.5,
                 98765,
                 "end".to_string(),
                 vec![1.0, 2.0, 3.0],
@@ -617,4 +549,4 @@ fn benchmark_constructors(c: &mut Criterion) {
 }
 
 criterion_group!(benches, benchmark_constructors);
-criterion_main!(benches);
+rust_examples::bench_main!(benches);
@@ -0,0 +1,231 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group};
+use std::error::Error;
+use std::fmt::{self, Write};
+
+// Failing requests per measured iteration.
+const CALLS: u64 = 10_000;
+
+// ---------------------------------------
+// Error types
+// ---------------------------------------
+
+// Closed set of failures, both of which the leaf produces.
+#[derive(Debug)]
+enum AppError {
+    NotFound(u64),
+    Storage(std::io::ErrorKind),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NotFound(id) => write!(f, "user {id} not found"),
+            AppError::Storage(kind) => write!(f, "storage error: {kind}"),
+        }
+    }
+}
+
+impl Error for AppError {}
+
+// One-word error: the payload lives behind a thin `Box`, so
+// `Result<u64, ThinError>` stays two words while `Box<dyn Error>` is a fat
+// pointer.
+#[derive(Debug)]
+struct ThinError(Box<AppError>);
+
+impl fmt::Display for ThinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for ThinError {}
+
+type DynError = Box<dyn Error + Send + Sync>;
+
+// A layer's context message over the error it wraps, building a chain
+// that `source()` walks back down to the root cause.
+#[derive(Debug)]
+struct Context {
+    message: String,
+    source: DynError,
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for Context {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+// ---------------------------------------
+// Layers: the leaf fails, every layer above propagates with `?`
+// ---------------------------------------
+
+// Every eighth lookup fails in storage rather than finding no user, so the
+// error paths carry (and render) both variants.
+fn leaf_error(id: u64) -> AppError {
+    let id = black_box(id);
+    if id % 8 == 7 {
+        AppError::Storage(std::io::ErrorKind::TimedOut)
+    } else {
+        AppError::NotFound(id)
+    }
+}
+
+#[inline(never)]
+fn layer_enum(depth: u32, id: u64) -> Result<u64, AppError> {
+    if depth == 0 {
+        return Err(leaf_error(id));
+    }
+    let v = layer_enum(depth - 1, id)?;
+    Ok(v + 1)
+}
+
+#[inline(never)]
+fn layer_thin(depth: u32, id: u64) -> Result<u64, ThinError> {
+    if depth == 0 {
+        return Err(ThinError(Box::new(leaf_error(id))));
+    }
+    let v = layer_thin(depth - 1, id)?;
+    Ok(v + 1)
+}
+
+#[inline(never)]
+fn layer_dyn(depth: u32, id: u64) -> Result<u64, DynError> {
+    if depth == 0 {
+        return Err(Box::new(leaf_error(id)));
+    }
+    let v = layer_dyn(depth - 1, id)?;
+    Ok(v + 1)
+}
+
+// Like `layer_dyn`, but each layer adds a formatted message and boxes again.
+#[inline(never)]
+fn layer_context(depth: u32, id: u64) -> Result<u64, DynError> {
+    if depth == 0 {
+        return Err(Box::new(leaf_error(id)));
+    }
+    let v = layer_context(depth - 1, id).map_err(|source| {
+        Box::new(Context {
+            message: format!("layer {depth} failed for user {id}"),
+            source,
+        }) as DynError
+    })?;
+    Ok(v + 1)
+}
+
+/// What a handler does before logging: the error and all its sources.
+fn render(error: &(dyn Error + 'static), out: &mut String) {
+    out.clear();
+    let mut current = Some(error);
+    while let Some(e) = current {
+        if !out.is_empty() {
+            out.push_str(": ");
+        }
+        write!(out, "{e}").unwrap();
+        current = e.source();
+    }
+}
+
+// ---------------------------------------
+// Benches
+// ---------------------------------------
+
+// Cost of building an error at the leaf and carrying it up `depth` layers.
+fn bench_propagation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("error_depth");
+    for depth in 1..=5 {
+        group.bench_function(BenchmarkId::new("enum", depth), |b| {
+            b.iter(|| {
+                let mut failed = 0u64;
+                for id in 0..CALLS {
+                    failed += black_box(layer_enum(depth, id)).is_err() as u64;
+                }
+                failed
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("thin_box", depth), |b| {
+            b.iter(|| {
+                let mut failed = 0u64;
+                for id in 0..CALLS {
+                    failed += black_box(layer_thin(depth, id)).is_err() as u64;
+                }
+                failed
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("boxed_dyn", depth), |b| {
+            b.iter(|| {
+                let mut failed = 0u64;
+                for id in 0..CALLS {
+                    failed += black_box(layer_dyn(depth, id)).is_err() as u64;
+                }
+                failed
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("context_chain", depth), |b| {
+            b.iter(|| {
+                let mut failed = 0u64;
+                for id in 0..CALLS {
+                    failed += black_box(layer_context(depth, id)).is_err() as u64;
+                }
+                failed
+            })
+        });
+    }
+    group.finish();
+}
+
+// Propagation plus turning the error into a log line at the top.
+fn bench_render(c: &mut Criterion) {
+    let mut group = c.benchmark_group("error_render");
+    for depth in [1, 5] {
+        group.bench_function(BenchmarkId::new("enum", depth), |b| {
+            let mut line = String::new();
+            b.iter(|| {
+                for id in 0..CALLS {
+                    if let Err(e) = layer_enum(depth, id) {
+                        render(&e, &mut line);
+                    }
+                }
+                black_box(line.len())
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("boxed_dyn", depth), |b| {
+            let mut line = String::new();
+            b.iter(|| {
+                for id in 0..CALLS {
+                    if let Err(e) = layer_dyn(depth, id) {
+                        render(&*e, &mut line);
+                    }
+                }
+                black_box(line.len())
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("context_chain", depth), |b| {
+            let mut line = String::new();
+            b.iter(|| {
+                for id in 0..CALLS {
+                    if let Err(e) = layer_context(depth, id) {
+                        render(&*e, &mut line);
+                    }
+                }
+                black_box(line.len())
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_propagation, bench_render);
+rust_examples::bench_main!(benches);
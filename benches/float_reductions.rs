@@ -0,0 +1,258 @@
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, Criterion, Throughput, black_box, criterion_group};
+use rust_examples::data::Gen;
+use rust_examples::results::{self, Accuracy};
+use std::iter::Sum;
+use std::ops::{Add, Mul, Sub};
+
+// Elements per reduction.
+const N: usize = 100_000;
+// Below this many elements pairwise summation adds in a plain loop.
+const PAIRWISE_BLOCK: usize = 128;
+
+// -----------------------------
+// Float types
+// -----------------------------
+
+trait Float:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Sum + 'static
+{
+    const NAME: &'static str;
+    const ZERO: Self;
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn to_f64(self) -> f64;
+    fn from_f64(x: f64) -> Self;
+}
+
+impl Float for f32 {
+    const NAME: &'static str = "f32";
+    const ZERO: f32 = 0.0;
+    fn mul_add(self, a: f32, b: f32) -> f32 {
+        f32::mul_add(self, a, b)
+    }
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+    fn from_f64(x: f64) -> f32 {
+        x as f32
+    }
+}
+
+impl Float for f64 {
+    const NAME: &'static str = "f64";
+    const ZERO: f64 = 0.0;
+    fn mul_add(self, a: f64, b: f64) -> f64 {
+        f64::mul_add(self, a, b)
+    }
+    fn to_f64(self) -> f64 {
+        self
+    }
+    fn from_f64(x: f64) -> f64 {
+        x
+    }
+}
+
+// -----------------------------
+// Inputs
+// -----------------------------
+
+#[derive(Clone, Copy)]
+enum Dist {
+    /// All positive, so rounding errors do not cancel out.
+    Uniform,
+    /// Mixed signs around zero: the result is small next to the terms, and
+    /// the relative error grows with it.
+    Cancelling,
+}
+
+impl Dist {
+    fn name(self) -> &'static str {
+        match self {
+            Dist::Uniform => "uniform",
+            Dist::Cancelling => "cancelling",
+        }
+    }
+
+    fn values<T: Float>(self, label: &str) -> Vec<T> {
+        let mut data = Gen::new(&format!("float_reductions/{}/{label}", self.name()));
+        let values = match self {
+            Dist::Uniform => data.uniform(N, 0.0..1.0),
+            Dist::Cancelling => data.normal(N, 0.0, 1.0),
+        };
+        values.into_iter().map(T::from_f64).collect()
+    }
+}
+
+// -----------------------------
+// Reference
+// -----------------------------
+//
+// Sum2 and Dot2 (Ogita, Rump and Oishi) in f64: as accurate as working in
+// twice f64's precision, far below the error of anything timed here.
+
+/// `a + b` and its exact rounding error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let b_virtual = s - a;
+    (s, (a - (s - b_virtual)) + (b - b_virtual))
+}
+
+fn reference_sum<T: Float>(v: &[T]) -> f64 {
+    let (mut hi, mut lo) = (0.0, 0.0);
+    for x in v {
+        let (s, e) = two_sum(hi, x.to_f64());
+        hi = s;
+        lo += e;
+    }
+    hi + lo
+}
+
+fn reference_dot<T: Float>(a: &[T], b: &[T]) -> f64 {
+    let (mut hi, mut lo) = (0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (x.to_f64(), y.to_f64());
+        // The product's rounding error, exact thanks to the single rounding of FMA.
+        let p = x * y;
+        let p_err = x.mul_add(y, -p);
+        let (s, e) = two_sum(hi, p);
+        hi = s;
+        lo += e + p_err;
+    }
+    hi + lo
+}
+
+/// Prints how far `got` is from `reference`, relative to it, and records it
+/// for `run-suite report` to show next to the timings of `id`.
+fn report_error<T: Float>(id: &str, got: T, reference: f64) {
+    let absolute = (got.to_f64() - reference).abs();
+    let relative = absolute / reference.abs();
+    println!("error {id}: rel={relative:.3e} abs={absolute:.3e}");
+    results::record_accuracy(id, Accuracy { relative, absolute });
+}
+
+// -----------------------------
+// Sums
+// -----------------------------
+
+#[allow(clippy::needless_range_loop)] // the plain loop is the baseline
+fn naive<T: Float>(v: &[T]) -> T {
+    let mut sum = T::ZERO;
+    for i in 0..v.len() {
+        sum = sum + v[i];
+    }
+    sum
+}
+
+fn sum<T: Float>(v: &[T]) -> T {
+    v.iter().copied().sum()
+}
+
+fn fold<T: Float>(v: &[T]) -> T {
+    v.iter().fold(T::ZERO, |acc, &x| acc + x)
+}
+
+// Error grows with log n rather than n.
+fn pairwise<T: Float>(v: &[T]) -> T {
+    if v.len() <= PAIRWISE_BLOCK {
+        return naive(v);
+    }
+    let (a, b) = v.split_at(v.len() / 2);
+    pairwise(a) + pairwise(b)
+}
+
+// Carries each addition's rounding error into the next one.
+fn kahan<T: Float>(v: &[T]) -> T {
+    let (mut sum, mut carry) = (T::ZERO, T::ZERO);
+    for &x in v {
+        let y = x - carry;
+        let t = sum + y;
+        carry = (t - sum) - y;
+        sum = t;
+    }
+    sum
+}
+
+// -----------------------------
+// Dot products
+// -----------------------------
+
+fn mul_then_add<T: Float>(a: &[T], b: &[T]) -> T {
+    let mut sum = T::ZERO;
+    for (&x, &y) in a.iter().zip(b) {
+        sum = sum + x * y;
+    }
+    sum
+}
+
+// One rounding per step instead of two. Without FMA in the target features
+// `mul_add` is a call into libm's software `fma`; the `bench-native` profile
+// alone does not enable it, only `-C target-cpu=native` does, which
+// `run-suite run --native` sets.
+fn mul_add<T: Float>(a: &[T], b: &[T]) -> T {
+    let mut sum = T::ZERO;
+    for (&x, &y) in a.iter().zip(b) {
+        sum = x.mul_add(y, sum);
+    }
+    sum
+}
+
+// -----------------------------
+// Benches
+// -----------------------------
+
+type SumVariant<T> = (&'static str, fn(&[T]) -> T);
+type DotVariant<T> = (&'static str, fn(&[T], &[T]) -> T);
+
+fn sum_variants<T: Float>() -> [SumVariant<T>; 5] {
+    [
+        ("naive", naive),
+        ("sum", sum),
+        ("fold", fold),
+        ("pairwise", pairwise),
+        ("kahan", kahan),
+    ]
+}
+
+fn dot_variants<T: Float>() -> [DotVariant<T>; 2] {
+    [("mul_then_add", mul_then_add), ("mul_add", mul_add)]
+}
+
+fn bench_sums<T: Float>(c: &mut Criterion, dist: Dist) {
+    let v: Vec<T> = dist.values("a");
+    let reference = reference_sum(&v);
+    let group = format!("float_sum_{}_{}", T::NAME, dist.name());
+    let mut g: BenchmarkGroup<'_, WallTime> = c.benchmark_group(&group);
+    g.throughput(Throughput::Elements(N as u64));
+    for (name, f) in sum_variants::<T>() {
+        report_error(&format!("{group}/{name}"), f(&v), reference);
+        g.bench_function(name, |b| b.iter(|| f(black_box(&v))));
+    }
+    g.finish();
+}
+
+fn bench_dots<T: Float>(c: &mut Criterion, dist: Dist) {
+    let (a, b): (Vec<T>, Vec<T>) = (dist.values("a"), dist.values("b"));
+    let reference = reference_dot(&a, &b);
+    let group = format!("float_dot_{}_{}", T::NAME, dist.name());
+    let mut g = c.benchmark_group(&group);
+    g.throughput(Throughput::Elements(N as u64));
+    for (name, f) in dot_variants::<T>() {
+        report_error(&format!("{group}/{name}"), f(&a, &b), reference);
+        g.bench_function(name, |bencher| {
+            bencher.iter(|| f(black_box(&a), black_box(&b)))
+        });
+    }
+    g.finish();
+}
+
+fn bench_reductions(c: &mut Criterion) {
+    for dist in [Dist::Uniform, Dist::Cancelling] {
+        bench_sums::<f32>(c, dist);
+        bench_sums::<f64>(c, dist);
+        bench_dots::<f32>(c, dist);
+        bench_dots::<f64>(c, dist);
+    }
+}
+
+criterion_group!(benches, bench_reductions);
+rust_examples::bench_main!(benches);
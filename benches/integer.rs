@@ -1,4 +1,11 @@
-use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use criterion::measurement::Measurement;
+use criterion::{Criterion, Throughput, black_box, criterion_group};
+use rust_examples::data::Gen;
+use rust_examples::perf;
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, RangeInclusive, Rem};
+use std::panic;
+use std::sync::OnceLock;
 
 //
 // i64 versions
@@ -9,7 +16,8 @@ fn takes_i64_by_move(value: i64) -> i64 {
 }
 
 fn takes_i64_by_ref(value: &i64) -> i64 {
-    black_box(value + 1) * value
+    // Overflows for the benched values; release builds always wrapped.
+    black_box(value + 1).wrapping_mul(*value)
 }
 
 fn takes_and_returns_i64_by_ref(value: &i64) -> &i64 {
@@ -25,42 +33,44 @@ fn takes_without_returning_i64_by_ref(value: &i64) {
     black_box(value + 1);
 }
 
-fn benchmark_i64_functions(c: &mut Criterion) {
+fn benchmark_i64_functions<M: Measurement + 'static>(c: &mut Criterion<M>, prefix: &str) {
     let value: i64 = 1234567890123456789;
 
-    c.bench_function("i64::takes_by_move", |b| {
+    c.bench_function(&format!("{prefix}i64::takes_by_move"), |b| {
         b.iter(|| {
             let result = takes_i64_by_move(black_box(value));
             black_box(result);
         })
     });
 
-    c.bench_function("i64::takes_by_ref", |b| {
+    c.bench_function(&format!("{prefix}i64::takes_by_ref"), |b| {
         b.iter(|| {
             let result = takes_i64_by_ref(black_box(&value));
             black_box(result);
         })
     });
 
-    c.bench_function("i64::takes_and_returns_by_ref", |b| {
+    c.bench_function(&format!("{prefix}i64::takes_and_returns_by_ref"), |b| {
         b.iter(|| {
             let result = takes_and_returns_i64_by_ref(black_box(&value));
             black_box(result);
         })
     });
 
-    c.bench_function("i64::takes_by_move_without_return", |b| {
+    c.bench_function(&format!("{prefix}i64::takes_by_move_without_return"), |b| {
         b.iter(|| {
-            let result = takes_i64_by_move_without_return(black_box(value));
-            black_box(result);
+            takes_i64_by_move_without_return(black_box(value));
         })
     });
 
-    c.bench_function("i64::takes_without_returning_by_ref", |b| {
-        b.iter(|| {
-            takes_without_returning_i64_by_ref(black_box(&value));
-        })
-    });
+    c.bench_function(
+        &format!("{prefix}i64::takes_without_returning_by_ref"),
+        |b| {
+            b.iter(|| {
+                takes_without_returning_i64_by_ref(black_box(&value));
+            })
+        },
+    );
 }
 
 //
@@ -72,7 +82,8 @@ fn takes_i128_by_move(value: i128) -> i128 {
 }
 
 fn takes_i128_by_ref(value: &i128) -> i128 {
-    black_box(value + 1) * value
+    // Overflows for the benched values; release builds always wrapped.
+    black_box(value + 1).wrapping_mul(*value)
 }
 
 fn takes_and_returns_i128_by_ref(value: &i128) -> &i128 {
@@ -88,43 +99,347 @@ fn takes_without_returning_i128_by_ref(value: &i128) {
     black_box(value + 1);
 }
 
-fn benchmark_i128_functions(c: &mut Criterion) {
+fn benchmark_i128_functions<M: Measurement + 'static>(c: &mut Criterion<M>, prefix: &str) {
     let value: i128 = 123456789012345678901234567890;
 
-    c.bench_function("i128::takes_by_move", |b| {
+    c.bench_function(&format!("{prefix}i128::takes_by_move"), |b| {
         b.iter(|| {
             let result = takes_i128_by_move(black_box(value));
             black_box(result);
         })
     });
 
-    c.bench_function("i128::takes_by_ref", |b| {
+    c.bench_function(&format!("{prefix}i128::takes_by_ref"), |b| {
         b.iter(|| {
             let result = takes_i128_by_ref(black_box(&value));
             black_box(result);
         })
     });
 
-    c.bench_function("i128::takes_and_returns_by_ref", |b| {
+    c.bench_function(&format!("{prefix}i128::takes_and_returns_by_ref"), |b| {
         b.iter(|| {
             let result = takes_and_returns_i128_by_ref(black_box(&value));
             black_box(result);
         })
     });
 
-    c.bench_function("i128::takes_by_move_without_return", |b| {
-        b.iter(|| {
-            let result = takes_i128_by_move_without_return(black_box(value));
-            black_box(result);
-        })
-    });
+    c.bench_function(
+        &format!("{prefix}i128::takes_by_move_without_return"),
+        |b| {
+            b.iter(|| {
+                takes_i128_by_move_without_return(black_box(value));
+            })
+        },
+    );
 
-    c.bench_function("i128::takes_without_returning_by_ref", |b| {
-        b.iter(|| {
-            takes_without_returning_i128_by_ref(black_box(&value));
-        })
+    c.bench_function(
+        &format!("{prefix}i128::takes_without_returning_by_ref"),
+        |b| {
+            b.iter(|| {
+                takes_without_returning_i128_by_ref(black_box(&value));
+            })
+        },
+    );
+}
+
+//
+// Width × operation × overflow handling
+//
+// Every kernel applies one operation element-wise over two black_boxed
+// slices. Operands never overflow (or divide by zero), so the matrix
+// measures what each form of overflow handling costs when it does not fire.
+// Run it again with overflow checks on to see what plain arithmetic costs
+// in debug-like builds:
+//
+//   cargo bench --profile bench-overflow --bench integer
+//
+
+// Elements per kernel call.
+const MATRIX_LEN: usize = 1_024;
+
+trait Int:
+    Copy
+    + Debug
+    + PartialEq
+    + Add<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + 'static
+{
+    const NAME: &'static str;
+    /// `n` uniform draws from `range`.
+    fn sample(data: &mut Gen, n: usize, range: RangeInclusive<Self>) -> Vec<Self>;
+    /// Operands whose sum cannot overflow.
+    fn add_operands() -> RangeInclusive<Self>;
+    /// Operands whose product cannot overflow.
+    fn mul_operands() -> RangeInclusive<Self>;
+    fn dividends() -> RangeInclusive<Self>;
+    /// Positive, so neither zero nor `MIN / -1` comes up.
+    fn divisors() -> RangeInclusive<Self>;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+    fn wrapping_div(self, rhs: Self) -> Self;
+    fn wrapping_rem(self, rhs: Self) -> Self;
+    fn saturating_add(self, rhs: Self) -> Self;
+    fn saturating_mul(self, rhs: Self) -> Self;
+    fn saturating_div(self, rhs: Self) -> Self;
+    fn overflowing_add(self, rhs: Self) -> (Self, bool);
+    fn overflowing_mul(self, rhs: Self) -> (Self, bool);
+    fn overflowing_div(self, rhs: Self) -> (Self, bool);
+    fn overflowing_rem(self, rhs: Self) -> (Self, bool);
+}
+
+// Forwards the trait methods to the inherent ones of the same name.
+macro_rules! forward {
+    ($($name:ident -> $ret:ty),* $(,)?) => {
+        $(
+            fn $name(self, rhs: Self) -> $ret {
+                Self::$name(self, rhs)
+            }
+        )*
+    };
+}
+
+macro_rules! impl_int {
+    ($($t:ident as $s:ident: $mul_lo:expr),* $(,)?) => {
+        $(
+            impl Int for $t {
+                const NAME: &'static str = stringify!($t);
+                #[allow(clippy::unnecessary_cast)]
+                fn sample(data: &mut Gen, n: usize, range: RangeInclusive<$t>) -> Vec<$t> {
+                    let range = *range.start() as $s..=*range.end() as $s;
+                    data.uniform(n, range).into_iter().map(|x| x as $t).collect()
+                }
+                fn add_operands() -> RangeInclusive<$t> {
+                    $t::MIN / 2..=$t::MAX / 2
+                }
+                fn mul_operands() -> RangeInclusive<$t> {
+                    let half = 1 << ($t::BITS / 2 - 1);
+                    $mul_lo(half)..=half - 1
+                }
+                fn dividends() -> RangeInclusive<$t> {
+                    $t::MIN..=$t::MAX
+                }
+                fn divisors() -> RangeInclusive<$t> {
+                    1..=1 << ($t::BITS / 2)
+                }
+                forward! {
+                    checked_add -> Option<Self>,
+                    checked_mul -> Option<Self>,
+                    checked_div -> Option<Self>,
+                    checked_rem -> Option<Self>,
+                    wrapping_add -> Self,
+                    wrapping_mul -> Self,
+                    wrapping_div -> Self,
+                    wrapping_rem -> Self,
+                    saturating_add -> Self,
+                    saturating_mul -> Self,
+                    saturating_div -> Self,
+                    overflowing_add -> (Self, bool),
+                    overflowing_mul -> (Self, bool),
+                    overflowing_div -> (Self, bool),
+                    overflowing_rem -> (Self, bool),
+                }
+            }
+        )*
+    };
+}
+
+// Unsigned operands start at zero; signed ones mirror around it. `rand`
+// only samples fixed-width integers, so the pointer-sized ones go through
+// their 64-bit counterparts.
+impl_int!(
+    u8 as u8: |_| 0,
+    u16 as u16: |_| 0,
+    u32 as u32: |_| 0,
+    u64 as u64: |_| 0,
+    u128 as u128: |_| 0,
+    usize as u64: |_| 0,
+    i8 as i8: |half: i8| -half,
+    i16 as i16: |half: i16| -half,
+    i32 as i32: |half: i32| -half,
+    i64 as i64: |half: i64| -half,
+    i128 as i128: |half: i128| -half,
+    isize as i64: |half: isize| -half,
+);
+
+// (overflow handling, kernel); a kernel returns whether anything overflowed.
+type Kernel<T> = (&'static str, fn(&[T], &[T], &mut [T]) -> bool);
+
+// One module per operation, each with the same set of kernels. `%` has no
+// saturating form.
+macro_rules! op_kernels {
+    ($op:ident, $sym:tt, $checked:ident, $wrapping:ident, $overflowing:ident $(, $saturating:ident)?) => {
+        mod $op {
+            use super::{Int, Kernel};
+
+            fn plain<T: Int>(a: &[T], b: &[T], out: &mut [T]) -> bool {
+                for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+                    *o = x $sym y;
+                }
+                false
+            }
+
+            // The `?` style: stop at the first overflow.
+            fn checked<T: Int>(a: &[T], b: &[T], out: &mut [T]) -> bool {
+                for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+                    match x.$checked(y) {
+                        Some(v) => *o = v,
+                        None => return true,
+                    }
+                }
+                false
+            }
+
+            fn wrapping<T: Int>(a: &[T], b: &[T], out: &mut [T]) -> bool {
+                for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+                    *o = x.$wrapping(y);
+                }
+                false
+            }
+
+            $(
+                fn saturating<T: Int>(a: &[T], b: &[T], out: &mut [T]) -> bool {
+                    for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+                        *o = x.$saturating(y);
+                    }
+                    false
+                }
+            )?
+
+            fn overflowing<T: Int>(a: &[T], b: &[T], out: &mut [T]) -> bool {
+                let mut any = false;
+                for ((o, &x), &y) in out.iter_mut().zip(a).zip(b) {
+                    let (v, overflowed) = x.$overflowing(y);
+                    *o = v;
+                    any |= overflowed;
+                }
+                any
+            }
+
+            pub fn kernels<T: Int>() -> Vec<Kernel<T>> {
+                let mut out: Vec<Kernel<T>> = vec![
+                    ("plain", plain),
+                    ("checked", checked),
+                    ("wrapping", wrapping),
+                ];
+                $(
+                    let _ = stringify!($saturating);
+                    out.push(("saturating", saturating));
+                )?
+                out.push(("overflowing", overflowing));
+                out
+            }
+        }
+    };
+}
+
+op_kernels!(add, +, checked_add, wrapping_add, overflowing_add, saturating_add);
+op_kernels!(mul, *, checked_mul, wrapping_mul, overflowing_mul, saturating_mul);
+op_kernels!(div, /, checked_div, wrapping_div, overflowing_div, saturating_div);
+op_kernels!(rem, %, checked_rem, wrapping_rem, overflowing_rem);
+
+/// Whether this build panics on arithmetic overflow; there is no stable
+/// `cfg` for it, so try one.
+fn overflow_checks() -> bool {
+    static ON: OnceLock<bool> = OnceLock::new();
+    *ON.get_or_init(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let on = panic::catch_unwind(|| black_box(u8::MAX) + black_box(1)).is_err();
+        panic::set_hook(hook);
+        on
+    })
+}
+
+fn bench_op<M: Measurement + 'static, T: Int>(
+    c: &mut Criterion<M>,
+    prefix: &str,
+    op: &str,
+    (a, b): (&[T], &[T]),
+    kernels: &[Kernel<T>],
+) {
+    let build = if overflow_checks() {
+        "overflow_checks"
+    } else {
+        "release"
+    };
+    let group = format!("{prefix}int_{op}_{}_{build}", T::NAME);
+
+    // No operand overflows, so every form must agree with plain arithmetic.
+    let mut expected = vec![a[0]; a.len()];
+    (kernels[0].1)(a, b, &mut expected);
+    for &(mode, f) in kernels {
+        let mut out = vec![a[0]; a.len()];
+        assert!(!f(a, b, &mut out), "{group}/{mode} overflowed");
+        assert_eq!(out, expected, "{group}/{mode} disagrees with plain");
+    }
+
+    let mut g = c.benchmark_group(group);
+    g.throughput(Throughput::Elements(a.len() as u64));
+    let mut out = vec![a[0]; a.len()];
+    for &(mode, f) in kernels {
+        g.bench_function(mode, |bencher| {
+            bencher.iter(|| {
+                black_box(f(black_box(a), black_box(b), &mut out));
+                black_box(&mut out);
+            })
+        });
+    }
+    g.finish();
+}
+
+fn bench_width<M: Measurement + 'static, T: Int>(c: &mut Criterion<M>, prefix: &str) {
+    let mut data = Gen::new(&format!("integer/{}", T::NAME));
+    let mut operands = |range: RangeInclusive<T>| T::sample(&mut data, MATRIX_LEN, range);
+    let (add_a, add_b) = (operands(T::add_operands()), operands(T::add_operands()));
+    let (mul_a, mul_b) = (operands(T::mul_operands()), operands(T::mul_operands()));
+    let (dividends, divisors) = (operands(T::dividends()), operands(T::divisors()));
+
+    bench_op(c, prefix, "add", (&add_a, &add_b), &add::kernels());
+    bench_op(c, prefix, "mul", (&mul_a, &mul_b), &mul::kernels());
+    bench_op(c, prefix, "div", (&dividends, &divisors), &div::kernels());
+    bench_op(c, prefix, "rem", (&dividends, &divisors), &rem::kernels());
+}
+
+fn benchmark_int_matrix<M: Measurement + 'static>(c: &mut Criterion<M>, prefix: &str) {
+    bench_width::<M, u8>(c, prefix);
+    bench_width::<M, u16>(c, prefix);
+    bench_width::<M, u32>(c, prefix);
+    bench_width::<M, u64>(c, prefix);
+    bench_width::<M, u128>(c, prefix);
+    bench_width::<M, usize>(c, prefix);
+    bench_width::<M, i8>(c, prefix);
+    bench_width::<M, i16>(c, prefix);
+    bench_width::<M, i32>(c, prefix);
+    bench_width::<M, i64>(c, prefix);
+    bench_width::<M, i128>(c, prefix);
+    bench_width::<M, isize>(c, prefix);
+}
+
+fn wall_time(c: &mut Criterion) {
+    benchmark_i64_functions(c, "");
+    benchmark_i128_functions(c, "");
+    benchmark_int_matrix(c, "");
+}
+
+// Wall time is too noisy for single-add differences; the same functions are
+// measured again with a CPU counter (instructions unless BENCH_PERF_COUNTER
+// says otherwise), under ids prefixed with the counter name.
+fn counters() {
+    perf::run_counted(|c, label| {
+        let prefix = format!("{label}/");
+        benchmark_i64_functions(c, &prefix);
+        benchmark_i128_functions(c, &prefix);
+        benchmark_int_matrix(c, &prefix);
     });
 }
 
-criterion_group!(benches, benchmark_i64_functions, benchmark_i128_functions);
-criterion_main!(benches);
+criterion_group!(benches, wall_time);
+rust_examples::bench_main!(benches, counters);
@@ -0,0 +1,540 @@
+// The indexed loops, bounds checks and all, are what the iterator forms are
+// compared against.
+#![allow(clippy::needless_range_loop)]
+
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, Criterion, Throughput, black_box, criterion_group};
+use rust_examples::data::{Gen, Lengths};
+
+// Unlike `loop_iterator`, which sums `0..n` ranges that LLVM can fold into
+// a closed form, every loop here reads a `black_box`ed slice.
+const N: usize = 10_000;
+
+// -----------------------------
+// Element types
+// -----------------------------
+
+/// An element type the adapters run over; each loop works on `weight`.
+trait Elem: Sized + 'static {
+    const NAME: &'static str;
+    fn make(n: usize) -> Vec<Self>;
+    fn weight(&self) -> u64;
+}
+
+impl Elem for u64 {
+    const NAME: &'static str = "u64";
+    fn make(n: usize) -> Vec<u64> {
+        Gen::new("iterator_slices/u64").uniform(n, 0..1 << 20)
+    }
+    fn weight(&self) -> u64 {
+        *self
+    }
+}
+
+// `weight` truncates, so the adapters above run integer arithmetic on the
+// floats; the `f32`-accumulating adapters further down cover float sums.
+impl Elem for f32 {
+    const NAME: &'static str = "f32";
+    fn make(n: usize) -> Vec<f32> {
+        let mut data = Gen::new("iterator_slices/f32");
+        data.normal(n, 1_000.0, 100.0)
+            .into_iter()
+            .map(|x| x as f32)
+            .collect()
+    }
+    fn weight(&self) -> u64 {
+        *self as u64
+    }
+}
+
+// Only the length: the bytes stay on the heap.
+impl Elem for String {
+    const NAME: &'static str = "string";
+    fn make(n: usize) -> Vec<String> {
+        Gen::new("iterator_slices/string").strings(n, Lengths::Uniform { min: 1, max: 24 })
+    }
+    fn weight(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+/// A `u64` behind a pointer, visited in an order unrelated to where it was
+/// allocated, so every element is a dependent load from somewhere else.
+struct Indirect(Box<u64>);
+
+impl Elem for Indirect {
+    const NAME: &'static str = "indirect";
+    fn make(n: usize) -> Vec<Indirect> {
+        let mut data = Gen::new("iterator_slices/indirect");
+        let mut v: Vec<Indirect> = data
+            .uniform(n, 0..1 << 20)
+            .into_iter()
+            .map(|x| Indirect(Box::new(x)))
+            .collect();
+        data.shuffle(&mut v);
+        v
+    }
+    fn weight(&self) -> u64 {
+        *self.0
+    }
+}
+
+// -----------------------------
+// Adapters
+// -----------------------------
+//
+// Each adapter is a pair computing the same value: an indexed loop, then
+// the iterator form.
+
+fn map_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut sum = 0;
+    for i in 0..v.len() {
+        sum += v[i].weight() * 3;
+    }
+    sum
+}
+
+fn map_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter().map(|x| x.weight() * 3).sum()
+}
+
+fn filter_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut sum = 0;
+    for i in 0..v.len() {
+        let w = v[i].weight();
+        if w & 1 == 1 {
+            sum += w;
+        }
+    }
+    sum
+}
+
+fn filter_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter().map(T::weight).filter(|w| w & 1 == 1).sum()
+}
+
+// Each element times its successor.
+fn zip_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut sum = 0;
+    for i in 0..v.len() - 1 {
+        sum += v[i].weight() * v[i + 1].weight();
+    }
+    sum
+}
+
+fn zip_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter()
+        .zip(&v[1..])
+        .map(|(a, b)| a.weight() * b.weight())
+        .sum()
+}
+
+fn skip_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut sum = 0;
+    for i in v.len() / 4..v.len() {
+        sum += v[i].weight();
+    }
+    sum
+}
+
+fn skip_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter().skip(v.len() / 4).map(T::weight).sum()
+}
+
+fn take_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut sum = 0;
+    for i in 0..v.len() / 2 {
+        sum += v[i].weight();
+    }
+    sum
+}
+
+fn take_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter().take(v.len() / 2).map(T::weight).sum()
+}
+
+fn flat_map_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut sum = 0;
+    for i in 0..v.len() {
+        let w = v[i].weight();
+        sum += w;
+        sum += w >> 1;
+    }
+    sum
+}
+
+fn flat_map_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter()
+        .flat_map(|x| {
+            let w = x.weight();
+            [w, w >> 1]
+        })
+        .sum()
+}
+
+// Never true, so both scan the whole slice.
+fn any_indexed<T: Elem>(v: &[T]) -> u64 {
+    for i in 0..v.len() {
+        if v[i].weight() == u64::MAX {
+            return 1;
+        }
+    }
+    0
+}
+
+fn any_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter().any(|x| x.weight() == u64::MAX) as u64
+}
+
+fn all_indexed<T: Elem>(v: &[T]) -> u64 {
+    for i in 0..v.len() {
+        if v[i].weight() == u64::MAX {
+            return 0;
+        }
+    }
+    1
+}
+
+fn all_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter().all(|x| x.weight() != u64::MAX) as u64
+}
+
+// An order-dependent hash, so the fold cannot be reassociated.
+fn fold_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut acc = 0u64;
+    for i in 0..v.len() {
+        acc = acc.wrapping_mul(31).wrapping_add(v[i].weight());
+    }
+    acc
+}
+
+fn fold_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter()
+        .fold(0u64, |acc, x| acc.wrapping_mul(31).wrapping_add(x.weight()))
+}
+
+fn collect_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut out = Vec::with_capacity(v.len());
+    for i in 0..v.len() {
+        out.push(v[i].weight());
+    }
+    black_box(&out).len() as u64
+}
+
+fn collect_iterator<T: Elem>(v: &[T]) -> u64 {
+    let out: Vec<u64> = v.iter().map(T::weight).collect();
+    black_box(&out).len() as u64
+}
+
+// Sum of the largest weight in each chunk of four.
+fn chunks_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < v.len() {
+        let mut max = 0;
+        for j in i..(i + 4).min(v.len()) {
+            max = max.max(v[j].weight());
+        }
+        sum += max;
+        i += 4;
+    }
+    sum
+}
+
+fn chunks_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.chunks(4)
+        .map(|c| c.iter().map(T::weight).max().unwrap_or(0))
+        .sum()
+}
+
+// Adjacent pairs in non-decreasing order.
+fn windows_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut count = 0;
+    for i in 0..v.len() - 1 {
+        count += (v[i].weight() <= v[i + 1].weight()) as u64;
+    }
+    count
+}
+
+fn windows_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.windows(2)
+        .filter(|w| w[0].weight() <= w[1].weight())
+        .count() as u64
+}
+
+// The two halves as separate slices.
+fn chain_indexed<T: Elem>(v: &[T]) -> u64 {
+    let (a, b) = v.split_at(v.len() / 2);
+    let mut sum = 0;
+    for i in 0..a.len() + b.len() {
+        let x = if i < a.len() { &a[i] } else { &b[i - a.len()] };
+        sum += x.weight();
+    }
+    sum
+}
+
+fn chain_iterator<T: Elem>(v: &[T]) -> u64 {
+    let (a, b) = v.split_at(v.len() / 2);
+    a.iter().chain(b).map(T::weight).sum()
+}
+
+// The fold hash again, back to front.
+fn rev_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut acc = 0u64;
+    for i in (0..v.len()).rev() {
+        acc = acc.wrapping_mul(31).wrapping_add(v[i].weight());
+    }
+    acc
+}
+
+fn rev_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter()
+        .rev()
+        .fold(0u64, |acc, x| acc.wrapping_mul(31).wrapping_add(x.weight()))
+}
+
+fn step_by_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < v.len() {
+        sum += v[i].weight();
+        i += 3;
+    }
+    sum
+}
+
+fn step_by_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter().step_by(3).map(T::weight).sum()
+}
+
+// Elements whose successor has the same parity, looking one ahead.
+fn peekable_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut count = 0;
+    for i in 0..v.len() - 1 {
+        count += (v[i].weight() & 1 == v[i + 1].weight() & 1) as u64;
+    }
+    count
+}
+
+fn peekable_iterator<T: Elem>(v: &[T]) -> u64 {
+    let mut count = 0;
+    let mut it = v.iter().peekable();
+    while let Some(x) = it.next() {
+        if let Some(next) = it.peek() {
+            count += (x.weight() & 1 == next.weight() & 1) as u64;
+        }
+    }
+    count
+}
+
+// Sum of the running maximum.
+fn scan_indexed<T: Elem>(v: &[T]) -> u64 {
+    let mut sum = 0;
+    let mut max = 0;
+    for i in 0..v.len() {
+        max = max.max(v[i].weight());
+        sum += max;
+    }
+    sum
+}
+
+fn scan_iterator<T: Elem>(v: &[T]) -> u64 {
+    v.iter()
+        .scan(0, |max, x| {
+            *max = (*max).max(x.weight());
+            Some(*max)
+        })
+        .sum()
+}
+
+// -----------------------------
+// Adapters with an f32 accumulator
+// -----------------------------
+//
+// Float addition is not associative, so LLVM must keep these sums in
+// order and cannot split them across SIMD lanes like the integer sums.
+
+fn map_f32_indexed(v: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    for i in 0..v.len() {
+        sum += v[i] * 3.0;
+    }
+    sum
+}
+
+fn map_f32_iterator(v: &[f32]) -> f32 {
+    v.iter().map(|x| x * 3.0).sum()
+}
+
+fn zip_f32_indexed(v: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    for i in 0..v.len() - 1 {
+        sum += v[i] * v[i + 1];
+    }
+    sum
+}
+
+fn zip_f32_iterator(v: &[f32]) -> f32 {
+    v.iter().zip(&v[1..]).map(|(a, b)| a * b).sum()
+}
+
+fn skip_f32_indexed(v: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    for i in v.len() / 4..v.len() {
+        sum += v[i];
+    }
+    sum
+}
+
+fn skip_f32_iterator(v: &[f32]) -> f32 {
+    v.iter().skip(v.len() / 4).sum()
+}
+
+fn take_f32_indexed(v: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    for i in 0..v.len() / 2 {
+        sum += v[i];
+    }
+    sum
+}
+
+fn take_f32_iterator(v: &[f32]) -> f32 {
+    v.iter().take(v.len() / 2).sum()
+}
+
+fn chain_f32_indexed(v: &[f32]) -> f32 {
+    let (a, b) = v.split_at(v.len() / 2);
+    let mut sum = 0.0;
+    for i in 0..a.len() + b.len() {
+        sum += if i < a.len() { a[i] } else { b[i - a.len()] };
+    }
+    sum
+}
+
+fn chain_f32_iterator(v: &[f32]) -> f32 {
+    let (a, b) = v.split_at(v.len() / 2);
+    a.iter().chain(b).sum()
+}
+
+fn step_by_f32_indexed(v: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    let mut i = 0;
+    while i < v.len() {
+        sum += v[i];
+        i += 3;
+    }
+    sum
+}
+
+fn step_by_f32_iterator(v: &[f32]) -> f32 {
+    v.iter().step_by(3).sum()
+}
+
+// Sum of the running maximum.
+fn scan_f32_indexed(v: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    let mut max = f32::MIN;
+    for i in 0..v.len() {
+        max = max.max(v[i]);
+        sum += max;
+    }
+    sum
+}
+
+fn scan_f32_iterator(v: &[f32]) -> f32 {
+    v.iter()
+        .scan(f32::MIN, |max, &x| {
+            *max = max.max(x);
+            Some(*max)
+        })
+        .sum()
+}
+
+// -----------------------------
+// Benches
+// -----------------------------
+
+// (adapter, indexed loop, iterator form)
+type Adapter<T, R = u64> = (&'static str, fn(&[T]) -> R, fn(&[T]) -> R);
+
+fn adapters<T: Elem>() -> [Adapter<T>; 17] {
+    [
+        ("map", map_indexed, map_iterator),
+        ("filter", filter_indexed, filter_iterator),
+        ("zip", zip_indexed, zip_iterator),
+        ("skip", skip_indexed, skip_iterator),
+        ("take", take_indexed, take_iterator),
+        ("flat_map", flat_map_indexed, flat_map_iterator),
+        ("any", any_indexed, any_iterator),
+        ("all", all_indexed, all_iterator),
+        ("fold", fold_indexed, fold_iterator),
+        ("collect", collect_indexed, collect_iterator),
+        ("chunks", chunks_indexed, chunks_iterator),
+        ("windows", windows_indexed, windows_iterator),
+        ("chain", chain_indexed, chain_iterator),
+        ("rev", rev_indexed, rev_iterator),
+        ("step_by", step_by_indexed, step_by_iterator),
+        ("peekable", peekable_indexed, peekable_iterator),
+        ("scan", scan_indexed, scan_iterator),
+    ]
+}
+
+const F32_ADAPTERS: [Adapter<f32, f32>; 7] = [
+    ("map", map_f32_indexed, map_f32_iterator),
+    ("zip", zip_f32_indexed, zip_f32_iterator),
+    ("skip", skip_f32_indexed, skip_f32_iterator),
+    ("take", take_f32_indexed, take_f32_iterator),
+    ("chain", chain_f32_indexed, chain_f32_iterator),
+    ("step_by", step_by_f32_indexed, step_by_f32_iterator),
+    ("scan", scan_f32_indexed, scan_f32_iterator),
+];
+
+fn bench_form<T, R>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    v: &[T],
+    f: fn(&[T]) -> R,
+) {
+    group.bench_function(name, |b| b.iter(|| f(black_box(v))));
+}
+
+fn bench_elem<T: Elem>(c: &mut Criterion) {
+    let v = T::make(N);
+    for (adapter, indexed, iterator) in adapters::<T>() {
+        let group = format!("slice_{adapter}_{}", T::NAME);
+        assert_eq!(indexed(&v), iterator(&v), "{group}: the two forms disagree");
+
+        let mut g = c.benchmark_group(group);
+        g.throughput(Throughput::Elements(N as u64));
+        bench_form(&mut g, "indexed", &v, indexed);
+        bench_form(&mut g, "iterator", &v, iterator);
+        g.finish();
+    }
+}
+
+// Same slice as `bench_elem::<f32>`, summed in `f32`.
+fn bench_f32_sums(c: &mut Criterion) {
+    let v = f32::make(N);
+    for (adapter, indexed, iterator) in F32_ADAPTERS {
+        let group = format!("slice_{adapter}_f32_sum");
+        // Both forms add in the same order, so the sums match exactly.
+        assert_eq!(indexed(&v), iterator(&v), "{group}: the two forms disagree");
+
+        let mut g = c.benchmark_group(group);
+        g.throughput(Throughput::Elements(N as u64));
+        bench_form(&mut g, "indexed", &v, indexed);
+        bench_form(&mut g, "iterator", &v, iterator);
+        g.finish();
+    }
+}
+
+fn bench_slices(c: &mut Criterion) {
+    bench_elem::<u64>(c);
+    bench_elem::<f32>(c);
+    bench_f32_sums(c);
+    bench_elem::<String>(c);
+    bench_elem::<Indirect>(c);
+}
+
+criterion_group!(benches, bench_slices);
+rust_examples::bench_main!(benches);
@@ -1,4 +1,4 @@
-use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use criterion::{Criterion, black_box, criterion_group};
 
 // ==== Basic Loop ====
 
@@ -21,6 +21,7 @@ fn map_without_sugar(n: u64) -> u64 {
     sum
 }
 
+#[allow(clippy::map_identity)] // the identity map is what is being measured
 fn map_for_loop(n: u64) -> u64 {
     (0..n).map(|x| x).sum()
 }
@@ -35,6 +36,7 @@ fn fold_without_sugar(n: u64) -> u64 {
     acc
 }
 
+#[allow(clippy::unnecessary_fold)] // fold vs. a hand-written loop is the comparison
 fn fold_for_loop(n: u64) -> u64 {
     (0..n).fold(0, |acc, x| acc + x)
 }
@@ -53,6 +55,7 @@ fn iterator_map_collect_without_sugar(n: u64) -> u64 {
     sum
 }
 
+#[allow(clippy::map_identity)]
 fn iterator_map_collect(n: u64) -> u64 {
     (0..n).map(|x| x).collect::<Vec<u64>>().iter().sum()
 }
@@ -268,4 +271,4 @@ fn bench_for_loops(c: &mut Criterion) {
 }
 
 criterion_group!(benches, bench_for_loops);
-criterion_main!(benches);
+rust_examples::bench_main!(benches);
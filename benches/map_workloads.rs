@@ -0,0 +1,347 @@
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, BenchmarkId, Criterion, Throughput, black_box, criterion_group};
+use rust_examples::data::{Gen, Zipf};
+use small_map::SmallMap;
+use std::collections::{BTreeMap, HashMap};
+use tinymap::ArrayMap;
+
+// Entries in the map before a workload starts. ArrayMap only runs at the
+// small size: its capacity is fixed on the stack.
+const SMALL: usize = 64;
+const LARGE: usize = 10_000;
+const SIZES: [usize; 2] = [SMALL, LARGE];
+
+// Operations per measured iteration.
+const OPS: usize = 1_000;
+// Keys per range scan.
+const RANGE_KEYS: u64 = 16;
+
+// -----------------------------
+// Stores under test
+// -----------------------------
+//
+// Present keys are the even numbers 0, 2, .., 2(n-1); odd keys always miss.
+
+trait Store {
+    const NAME: &'static str;
+    fn build(entries: &[(u64, u64)]) -> Self;
+    fn get(&self, k: u64) -> Option<u64>;
+    fn insert(&mut self, k: u64, v: u64);
+    fn remove(&mut self, k: u64) -> Option<u64>;
+    /// Sum of values with keys in `lo..hi`.
+    fn range_sum(&self, lo: u64, hi: u64) -> u64;
+    fn iter_sum(&self) -> u64;
+}
+
+impl Store for HashMap<u64, u64> {
+    const NAME: &'static str = "hashmap";
+    fn build(entries: &[(u64, u64)]) -> Self {
+        entries.iter().copied().collect()
+    }
+    fn get(&self, k: u64) -> Option<u64> {
+        HashMap::get(self, &k).copied()
+    }
+    fn insert(&mut self, k: u64, v: u64) {
+        HashMap::insert(self, k, v);
+    }
+    fn remove(&mut self, k: u64) -> Option<u64> {
+        HashMap::remove(self, &k)
+    }
+    // Unordered: a range scan has to look at every entry.
+    fn range_sum(&self, lo: u64, hi: u64) -> u64 {
+        self.iter()
+            .filter(|(k, _)| (lo..hi).contains(*k))
+            .map(|(_, v)| v)
+            .sum()
+    }
+    fn iter_sum(&self) -> u64 {
+        self.values().sum()
+    }
+}
+
+impl Store for BTreeMap<u64, u64> {
+    const NAME: &'static str = "btreemap";
+    fn build(entries: &[(u64, u64)]) -> Self {
+        entries.iter().copied().collect()
+    }
+    fn get(&self, k: u64) -> Option<u64> {
+        BTreeMap::get(self, &k).copied()
+    }
+    fn insert(&mut self, k: u64, v: u64) {
+        BTreeMap::insert(self, k, v);
+    }
+    fn remove(&mut self, k: u64) -> Option<u64> {
+        BTreeMap::remove(self, &k)
+    }
+    fn range_sum(&self, lo: u64, hi: u64) -> u64 {
+        self.range(lo..hi).map(|(_, v)| v).sum()
+    }
+    fn iter_sum(&self) -> u64 {
+        self.values().sum()
+    }
+}
+
+// Inline up to SMALLMAP_INLINE entries, a heap hash map beyond.
+const SMALLMAP_INLINE: usize = 128;
+
+impl Store for SmallMap<SMALLMAP_INLINE, u64, u64> {
+    const NAME: &'static str = "smallmap";
+    fn build(entries: &[(u64, u64)]) -> Self {
+        let mut m = SmallMap::new();
+        for &(k, v) in entries {
+            m.insert(k, v);
+        }
+        m
+    }
+    fn get(&self, k: u64) -> Option<u64> {
+        SmallMap::get(self, &k).copied()
+    }
+    fn insert(&mut self, k: u64, v: u64) {
+        SmallMap::insert(self, k, v);
+    }
+    fn remove(&mut self, k: u64) -> Option<u64> {
+        SmallMap::remove(self, &k)
+    }
+    fn range_sum(&self, lo: u64, hi: u64) -> u64 {
+        self.iter()
+            .filter(|(k, _)| (lo..hi).contains(*k))
+            .map(|(_, v)| v)
+            .sum()
+    }
+    fn iter_sum(&self) -> u64 {
+        self.iter().map(|(_, v)| v).sum()
+    }
+}
+
+// Churn can at most double the map: every odd key inserted too.
+const ARRAYMAP_CAPACITY: usize = 2 * SMALL;
+
+impl Store for ArrayMap<u64, u64, ARRAYMAP_CAPACITY> {
+    const NAME: &'static str = "arraymap";
+    fn build(entries: &[(u64, u64)]) -> Self {
+        entries.iter().copied().collect()
+    }
+    fn get(&self, k: u64) -> Option<u64> {
+        ArrayMap::get(self, &k).copied()
+    }
+    fn insert(&mut self, k: u64, v: u64) {
+        ArrayMap::insert(self, k, v);
+    }
+    fn remove(&mut self, k: u64) -> Option<u64> {
+        ArrayMap::remove(self, &k)
+    }
+    fn range_sum(&self, lo: u64, hi: u64) -> u64 {
+        self.iter()
+            .filter(|(k, _)| (lo..hi).contains(*k))
+            .map(|(_, v)| v)
+            .sum()
+    }
+    fn iter_sum(&self) -> u64 {
+        self.values().sum()
+    }
+}
+
+// Sorted by key; lookups binary search, inserts and removes shift the tail.
+struct SortedVec(Vec<(u64, u64)>);
+
+impl Store for SortedVec {
+    const NAME: &'static str = "sorted_vec";
+    fn build(entries: &[(u64, u64)]) -> Self {
+        let mut v = entries.to_vec();
+        v.sort_unstable();
+        SortedVec(v)
+    }
+    fn get(&self, k: u64) -> Option<u64> {
+        self.0
+            .binary_search_by_key(&k, |&(k, _)| k)
+            .ok()
+            .map(|i| self.0[i].1)
+    }
+    fn insert(&mut self, k: u64, v: u64) {
+        match self.0.binary_search_by_key(&k, |&(k, _)| k) {
+            Ok(i) => self.0[i].1 = v,
+            Err(i) => self.0.insert(i, (k, v)),
+        }
+    }
+    fn remove(&mut self, k: u64) -> Option<u64> {
+        let i = self.0.binary_search_by_key(&k, |&(k, _)| k).ok()?;
+        Some(self.0.remove(i).1)
+    }
+    fn range_sum(&self, lo: u64, hi: u64) -> u64 {
+        let start = self.0.partition_point(|&(k, _)| k < lo);
+        self.0[start..]
+            .iter()
+            .take_while(|&&(k, _)| k < hi)
+            .map(|&(_, v)| v)
+            .sum()
+    }
+    fn iter_sum(&self) -> u64 {
+        self.0.iter().map(|&(_, v)| v).sum()
+    }
+}
+
+// -----------------------------
+// Key distributions
+// -----------------------------
+
+#[derive(Clone, Copy)]
+enum Dist {
+    Uniform,
+    /// Exponent 1: rank r is drawn with probability proportional to 1/(r+1).
+    Zipf,
+}
+
+impl Dist {
+    fn name(self) -> &'static str {
+        match self {
+            Dist::Uniform => "uniform",
+            Dist::Zipf => "zipf",
+        }
+    }
+}
+
+/// Draws ranks in `0..n`. Ranks are mapped to keys through a seeded
+/// permutation, so the hot keys are scattered over the key space instead of
+/// sitting together at the front of an ordered map.
+struct Sampler {
+    data: Gen,
+    zipf: Option<Zipf>,
+    order: Vec<u64>,
+}
+
+impl Sampler {
+    fn new(n: usize, dist: Dist) -> Sampler {
+        let mut data = Gen::new(&format!("map_workloads/{n}"));
+        let mut order: Vec<u64> = (0..n as u64).collect();
+        data.shuffle(&mut order);
+        let zipf = match dist {
+            Dist::Uniform => None,
+            Dist::Zipf => Some(Zipf::new(n, 1.0)),
+        };
+        Sampler { data, zipf, order }
+    }
+
+    /// A slot in `0..n`, i.e. the index of a present key.
+    fn slot(&mut self) -> u64 {
+        let rank = match &self.zipf {
+            None => self.data.below(0..self.order.len()),
+            Some(zipf) => zipf.sample(&mut self.data),
+        };
+        self.order[rank]
+    }
+
+    fn percent(&mut self) -> u32 {
+        self.data.below(0..100)
+    }
+}
+
+// -----------------------------
+// Workloads
+// -----------------------------
+
+#[derive(Clone, Copy)]
+enum Op {
+    Get(u64),
+    Put(u64, u64),
+    /// Insert if absent, remove if present.
+    Toggle(u64),
+    Range(u64),
+}
+
+const PROFILES: [&str; 4] = ["hit", "miss", "churn", "range"];
+
+// Pre-generated so the timed loop only runs the operations.
+fn ops(profile: &str, n: usize, dist: Dist) -> Vec<Op> {
+    let mut s = Sampler::new(n, dist);
+    (0..OPS)
+        .map(|i| {
+            let slot = s.slot();
+            match profile {
+                // 95% reads of present keys, 5% overwrites.
+                "hit" if s.percent() < 95 => Op::Get(2 * slot),
+                // 95% reads of absent (odd) keys, 5% overwrites.
+                "miss" if s.percent() < 95 => Op::Get(2 * slot + 1),
+                "hit" | "miss" => Op::Put(2 * slot, i as u64),
+                // Over the key space of 2n, so the map hovers around n
+                // entries with about as many inserts as removes.
+                "churn" => Op::Toggle(2 * slot + (s.percent() % 2) as u64),
+                _ => Op::Range(2 * slot),
+            }
+        })
+        .collect()
+}
+
+fn apply<S: Store>(store: &mut S, ops: &[Op]) -> u64 {
+    let mut acc = 0u64;
+    for &op in ops {
+        match op {
+            Op::Get(k) => acc = acc.wrapping_add(store.get(k).unwrap_or(1)),
+            Op::Put(k, v) => store.insert(k, v),
+            Op::Toggle(k) => {
+                if store.remove(k).is_none() {
+                    store.insert(k, k);
+                }
+            }
+            Op::Range(k) => acc = acc.wrapping_add(store.range_sum(k, k + 2 * RANGE_KEYS)),
+        }
+    }
+    acc
+}
+
+fn entries(n: usize) -> Vec<(u64, u64)> {
+    (0..n as u64).map(|i| (2 * i, i)).collect()
+}
+
+fn bench_store<S: Store>(group: &mut BenchmarkGroup<'_, WallTime>, n: usize, ops: &[Op]) {
+    let mut store = black_box(S::build(&entries(n)));
+    group.bench_function(BenchmarkId::new(S::NAME, n), |b| {
+        b.iter(|| apply(&mut store, black_box(ops)))
+    });
+}
+
+fn bench_workloads(c: &mut Criterion) {
+    for profile in PROFILES {
+        for dist in [Dist::Uniform, Dist::Zipf] {
+            let mut group = c.benchmark_group(format!("workload_{profile}_{}", dist.name()));
+            group.throughput(Throughput::Elements(OPS as u64));
+            for n in SIZES {
+                let ops = ops(profile, n, dist);
+                bench_store::<HashMap<u64, u64>>(&mut group, n, &ops);
+                bench_store::<BTreeMap<u64, u64>>(&mut group, n, &ops);
+                bench_store::<SmallMap<SMALLMAP_INLINE, u64, u64>>(&mut group, n, &ops);
+                if n == SMALL {
+                    bench_store::<ArrayMap<u64, u64, ARRAYMAP_CAPACITY>>(&mut group, n, &ops);
+                }
+                bench_store::<SortedVec>(&mut group, n, &ops);
+            }
+            group.finish();
+        }
+    }
+}
+
+// Visiting every entry does not depend on the key distribution.
+fn bench_iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("workload_iterate");
+    for n in SIZES {
+        group.throughput(Throughput::Elements(n as u64));
+        let entries = entries(n);
+        iterate::<HashMap<u64, u64>>(&mut group, &entries);
+        iterate::<BTreeMap<u64, u64>>(&mut group, &entries);
+        iterate::<SmallMap<SMALLMAP_INLINE, u64, u64>>(&mut group, &entries);
+        if n == SMALL {
+            iterate::<ArrayMap<u64, u64, ARRAYMAP_CAPACITY>>(&mut group, &entries);
+        }
+        iterate::<SortedVec>(&mut group, &entries);
+    }
+    group.finish();
+}
+
+fn iterate<S: Store>(group: &mut BenchmarkGroup<'_, WallTime>, entries: &[(u64, u64)]) {
+    let store = black_box(S::build(entries));
+    group.bench_function(BenchmarkId::new(S::NAME, entries.len()), |b| {
+        b.iter(|| store.iter_sum())
+    });
+}
+
+criterion_group!(benches, bench_workloads, bench_iterate);
+rust_examples::bench_main!(benches);
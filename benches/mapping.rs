@@ -1,154 +1,7 @@
-use criterion::{Criterion, black_box, criterion_group, criterion_main};
-use std::collections::HashMap;
-
-// Define the structures
-#[derive(Clone)]
-struct Small {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<u8>,
-    e: HashMap<String, i32>,
-    f: bool,
-    g: Option<i32>,
-    h: Vec<String>,
-    i: f32,
-    j: HashMap<String, Vec<i32>>,
-}
-
-#[derive(Clone)]
-struct SmallV2 {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<u8>,
-    e: HashMap<String, i32>,
-    f: bool,
-    g: Option<i32>,
-    h: Vec<String>,
-    i: f32,
-    j: HashMap<String, Vec<i32>>,
-}
-
-#[derive(Clone)]
-struct Medium {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<u8>,
-    e: HashMap<String, i32>,
-    f: i64,
-    g: Vec<i32>,
-    h: String,
-    i: HashMap<i32, String>,
-    j: f32,
-    k: Option<i32>,
-    l: Vec<f64>,
-    m: bool,
-    n: Vec<String>,
-    o: HashMap<i64, f64>,
-    p: String,
-    q: Option<Vec<i32>>,
-    r: Vec<u32>,
-    s: HashMap<String, Vec<u8>>,
-    t: f64,
-}
-
-#[derive(Clone)]
-struct MediumV2 {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<u8>,
-    e: HashMap<String, i32>,
-    f: i64,
-    g: Vec<i32>,
-    h: String,
-    i: HashMap<i32, String>,
-    j: f32,
-    k: Option<i32>,
-    l: Vec<f64>,
-    m: bool,
-    n: Vec<String>,
-    o: HashMap<i64, f64>,
-    p: String,
-    q: Option<Vec<i32>>,
-    r: Vec<u32>,
-    s: HashMap<String, Vec<u8>>,
-    t: f64,
-}
-
-#[derive(Clone)]
-struct Large {
-    a: i64,
-    b: f32,
-    c: String,
-    d: Vec<u32>,
-    e: HashMap<String, Vec<u8>>,
-    f: Option<u64>,
-    g: i64,
-    h: Vec<i32>,
-    i: HashMap<i32, String>,
-    j: f64,
-    k: u32,
-    l: Vec<f64>,
-    m: String,
-    n: HashMap<i64, i32>,
-    o: Vec<String>,
-    p: Vec<i64>,
-    q: Vec<u8>,
-    r: HashMap<String, String>,
-    s: Vec<u32>,
-    t: Option<Vec<i32>>,
-    u: f64,
-    v: String,
-    w: i32,
-    x: f32,
-    y: i64,
-    z: String,
-    aa: Vec<f32>,
-    ab: u64,
-    ac: HashMap<String, Vec<i32>>,
-    ad: Option<String>,
-    ae: Vec<String>,
-    af: HashMap<i64, f64>,
-}
-
-#[derive(Clone)]
-struct LargeV2 {
-    a: i64,
-    b: f32,
-    c: String,
-    d: Vec<u32>,
-    e: HashMap<String, Vec<u8>>,
-    f: Option<u64>,
-    g: i64,
-    h: Vec<i32>,
-    i: HashMap<i32, String>,
-    j: f64,
-    k: u32,
-    l: Vec<f64>,
-    m: String,
-    n: HashMap<i64, i32>,
-    o: Vec<String>,
-    p: Vec<i64>,
-    q: Vec<u8>,
-    r: HashMap<String, String>,
-    s: Vec<u32>,
-    t: Option<Vec<i32>>,
-    u: f64,
-    v: String,
-    w: i32,
-    x: f32,
-    y: i64,
-    z: String,
-    aa: Vec<f32>,
-    ab: u64,
-    ac: HashMap<String, Vec<i32>>,
-    ad: Option<String>,
-    ae: Vec<String>,
-    af: HashMap<i64, f64>,
-}
+use criterion::{Criterion, black_box, criterion_group};
+use rust_examples::fixtures::{
+    BENCH_SIZE, Fixture, Large, LargeV2, Medium, MediumV2, Small, SmallV2,
+};
 
 // --- MAPPING FUNCTIONS ---
 
@@ -392,18 +245,7 @@ fn benchmark_mapping(c: &mut Criterion) {
     // Benchmark for Small -> SmallV2 (Move)
     c.bench_function("map_small_to_v2_move", |b| {
         b.iter(|| {
-            let s = Small {
-                a: 1,
-                b: "Hello".to_string(),
-                c: 3.14,
-                d: vec![1, 2, 3],
-                e: HashMap::new(),
-                f: true,
-                g: Some(42),
-                h: vec!["world".to_string()],
-                i: 2.5,
-                j: HashMap::new(),
-            };
+            let s = Small::build_flat(BENCH_SIZE);
             let _ = black_box(map_small_to_v2_move(s));
         })
     });
@@ -411,28 +253,7 @@ fn benchmark_mapping(c: &mut Criterion) {
     // Benchmark for Medium -> MediumV2 (Move)
     c.bench_function("map_medium_to_v2_move", |b| {
         b.iter(|| {
-            let m = Medium {
-                a: 1,
-                b: "Medium".to_string(),
-                c: 6.28,
-                d: vec![4, 5, 6],
-                e: HashMap::new(),
-                f: 64,
-                g: vec![10, 20, 30],
-                h: "medium".to_string(),
-                i: HashMap::new(),
-                j: 1.23,
-                k: Some(99),
-                l: vec![1.1, 2.2, 3.3],
-                m: false,
-                n: vec!["one".to_string(), "two".to_string()],
-                o: HashMap::new(),
-                p: "p".to_string(),
-                q: Some(vec![7, 8, 9]),
-                r: vec![100, 200],
-                s: HashMap::new(),
-                t: 9.81,
-            };
+            let m = Medium::build_flat(BENCH_SIZE);
             let _ = black_box(map_medium_to_v2_move(m));
         })
     });
@@ -440,40 +261,7 @@ fn benchmark_mapping(c: &mut Criterion) {
     // Benchmark for Large -> LargeV2 (Move)
     c.bench_function("map_large_to_v2_move", |b| {
         b.iter(|| {
-            let l = Large {
-                a: 1000,
-                b: 3.14,
-                c: "Large".to_string(),
-                d: vec![1, 2, 3],
-                e: HashMap::new(),
-                f: Some(42),
-                g: 12345,
-                h: vec![10, 20, 30],
-                i: HashMap::new(),
-                j: 6.28,
-                k: 99,
-                l: vec![1.1, 2.2, 3.3],
-                m: "Hello".to_string(),
-                n: HashMap::new(),
-                o: vec!["a".to_string(), "b".to_string()],
-                p: vec![100, 200],
-                q: vec![1, 2, 3],
-                r: HashMap::new(),
-                s: vec![10, 20, 30],
-                t: Some(vec![4, 5, 6]),
-                u: 9.81,
-                v: "world".to_string(),
-                w: 42,
-                x: 2.71,
-                y: 98765,
-                z: "end".to_string(),
-                aa: vec![1.0, 2.0, 3.0],
-                ab: 100000,
-                ac: HashMap::new(),
-                ad: Some("optional".to_string()),
-                ae: vec!["test".to_string()],
-                af: HashMap::new(),
-            };
+            let l = Large::build_flat(BENCH_SIZE);
             let _ = black_box(map_large_to_v2_move(l));
         })
     });
@@ -481,86 +269,21 @@ fn benchmark_mapping(c: &mut Criterion) {
     // Clone benchmarks
     c.bench_function("map_small_to_v2_clone", |b| {
         b.iter(|| {
-            let s = Small {
-                a: 1,
-                b: "Hello".to_string(),
-                c: 3.14,
-                d: vec![1, 2, 3],
-                e: HashMap::new(),
-                f: true,
-                g: Some(42),
-                h: vec!["world".to_string()],
-                i: 2.5,
-                j: HashMap::new(),
-            };
+            let s = Small::build_flat(BENCH_SIZE);
             let _ = black_box(map_small_to_v2_clone(s));
         })
     });
 
     c.bench_function("map_medium_to_v2_clone", |b| {
         b.iter(|| {
-            let m = Medium {
-                a: 1,
-                b: "Medium".to_string(),
-                c: 6.28,
-                d: vec![4, 5, 6],
-                e: HashMap::new(),
-                f: 64,
-                g: vec![10, 20, 30],
-                h: "medium".to_string(),
-                i: HashMap::new(),
-                j: 1.23,
-                k: Some(99),
-                l: vec![1.1, 2.2, 3.3],
-                m: false,
-                n: vec!["one".to_string(), "two".to_string()],
-                o: HashMap::new(),
-                p: "p".to_string(),
-                q: Some(vec![7, 8, 9]),
-                r: vec![100, 200],
-                s: HashMap::new(),
-                t: 9.81,
-            };
+            let m = Medium::build_flat(BENCH_SIZE);
             let _ = black_box(map_medium_to_v2_clone(m));
         })
     });
 
     c.bench_function("map_large_to_v2_clone", |b| {
         b.iter(|| {
-            let l = Large {
-                a: 1000,
-                b: 3.14,
-                c: "Large".to_string(),
-                d: vec![1, 2, 3],
-                e: HashMap::new(),
-                f: Some(42),
-                g: 12345,
-                h: vec![10, 20, 30],
-                i: HashMap::new(),
-                j: 6.28,
-                k: 99,
-                l: vec![1.1, 2.2, 3.3],
-                m: "Hello".to_string(),
-                n: HashMap::new(),
-                o: vec!["a".to_string(), "b".to_string()],
-                p: vec![100, 200],
-                q: vec![1, 2, 3],
-                r: HashMap::new(),
-                s: vec![10, 20, 30],
-                t: Some(vec![4, 5, 6]),
-                u: 9.81,
-                v: "world".to_string(),
-                w: 42,
-                x: 2.71,
-                y: 98765,
-                z: "end".to_string(),
-                aa: vec![1.0, 2.0, 3.0],
-                ab: 100000,
-                ac: HashMap::new(),
-                ad: Some("optional".to_string()),
-                ae: vec!["test".to_string()],
-                af: HashMap::new(),
-            };
+            let l = Large::build_flat(BENCH_SIZE);
             let _ = black_box(map_large_to_v2_clone(l));
         })
     });
@@ -568,90 +291,25 @@ fn benchmark_mapping(c: &mut Criterion) {
     // Reference benchmarks
     c.bench_function("map_small_to_v2_reference", |b| {
         b.iter(|| {
-            let s = Small {
-                a: 1,
-                b: "Hello".to_string(),
-                c: 3.14,
-                d: vec![1, 2, 3],
-                e: HashMap::new(),
-                f: true,
-                g: Some(42),
-                h: vec!["world".to_string()],
-                i: 2.5,
-                j: HashMap::new(),
-            };
+            let s = Small::build_flat(BENCH_SIZE);
             let _ = black_box(map_small_to_v2_reference(&s));
         })
     });
 
     c.bench_function("map_medium_to_v2_reference", |b| {
         b.iter(|| {
-            let m = Medium {
-                a: 1,
-                b: "Medium".to_string(),
-                c: 6.28,
-                d: vec![4, 5, 6],
-                e: HashMap::new(),
-                f: 64,
-                g: vec![10, 20, 30],
-                h: "medium".to_string(),
-                i: HashMap::new(),
-                j: 1.23,
-                k: Some(99),
-                l: vec![1.1, 2.2, 3.3],
-                m: false,
-                n: vec!["one".to_string(), "two".to_string()],
-                o: HashMap::new(),
-                p: "p".to_string(),
-                q: Some(vec![7, 8, 9]),
-                r: vec![100, 200],
-                s: HashMap::new(),
-                t: 9.81,
-            };
+            let m = Medium::build_flat(BENCH_SIZE);
             let _ = black_box(map_medium_to_v2_reference(&m));
         })
     });
 
     c.bench_function("map_large_to_v2_reference", |b| {
         b.iter(|| {
-            let l = Large {
-                a: 1000,
-                b: 3.14,
-                c: "Large".to_string(),
-                d: vec![1, 2, 3],
-                e: HashMap::new(),
-                f: Some(42),
-                g: 12345,
-                h: vec![10, 20, 30],
-                i: HashMap::new(),
-                j: 6.28,
-                k: 99,
-                l: vec![1.1, 2.2, 3.3],
-                m: "Hello".to_string(),
-                n: HashMap::new(),
-                o: vec!["a".to_string(), "b".to_string()],
-                p: vec![100, 200],
-                q: vec![1, 2, 3],
-                r: HashMap::new(),
-                s: vec![10, 20, 30],
-                t: Some(vec![4, 5, 6]),
-                u: 9.81,
-                v: "world".to_string(),
-                w: 42,
-                x: 2.71,
-                y: 98765,
-                z: "end".to_string(),
-                aa: vec![1.0, 2.0, 3.0],
-                ab: 100000,
-                ac: HashMap::new(),
-                ad: Some("optional".to_string()),
-                ae: vec!["test".to_string()],
-                af: HashMap::new(),
-            };
+            let l = Large::build_flat(BENCH_SIZE);
             let _ = black_box(map_large_to_v2_reference(&l));
         })
     });
 }
 
 criterion_group!(benches, benchmark_mapping);
-criterion_main!(benches);
+rust_examples::bench_main!(benches);
@@ -1,33 +1,7 @@
 use criterion::{Criterion, black_box};
-use std::collections::HashMap;
-
-#[derive(Clone)]
-struct Small {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<i32>,
-    e: HashMap<String, i32>,
-    f: bool,
-    g: Option<i32>,
-    h: Vec<String>,
-    i: f64,
-    j: HashMap<String, i32>,
-}
-
-#[derive(Clone)]
-struct SmallV2 {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<i32>,
-    e: HashMap<String, i32>,
-    f: bool,
-    g: Option<i32>,
-    h: Vec<String>,
-    i: f64,
-    j: HashMap<String, i32>,
-}
+use rust_examples::fixtures::{
+    BENCH_SIZE, Fixture, Large, LargeV2, Medium, MediumV2, Small, SmallV2,
+};
 
 // Map and return mapped struct for Small
 fn map_small(input: Small) -> SmallV2 {
@@ -50,47 +24,6 @@ fn return_small(input: Small) -> Small {
     input
 }
 
-
-#[derive(Clone)]
-struct Medium {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<i32>,
-    e: HashMap<String, i32>,
-    f: bool,
-    g: Option<i32>,
-    h: Vec<String>,
-    i: f64,
-    j: HashMap<String, i32>,
-
-    k: String,
-    l: Vec<u8>,
-    m: Option<String>,
-    n: HashMap<String, String>,
-    o: bool,
-}
-
-#[derive(Clone)]
-struct MediumV2 {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<i32>,
-    e: HashMap<String, i32>,
-    f: bool,
-    g: Option<i32>,
-    h: Vec<String>,
-    i: f64,
-    j: HashMap<String, i32>,
-
-    k: String,
-    l: Vec<u8>,
-    m: Option<String>,
-    n: HashMap<String, String>,
-    o: bool,
-}
-
 // Map and return mapped struct for Medium
 fn map_medium(input: Medium) -> MediumV2 {
     MediumV2 {
@@ -99,17 +32,21 @@ fn map_medium(input: Medium) -> MediumV2 {
         c: input.c + 1.0,
         d: input.d,
         e: input.e,
-        f: input.f,
-        g: input.g.map(|v| v + 1),
+        f: input.f + 1,
+        g: input.g,
         h: input.h,
-        i: input.i + 1.0,
-        j: input.j,
-
-        k: input.k,
+        i: input.i,
+        j: input.j + 1.0,
+        k: input.k.map(|v| v + 1),
         l: input.l,
-        m: input.m,
+        m: !input.m,
         n: input.n,
         o: input.o,
+        p: input.p,
+        q: input.q,
+        r: input.r,
+        s: input.s,
+        t: input.t + 1.0,
     }
 }
 
@@ -118,84 +55,41 @@ fn return_medium(input: Medium) -> Medium {
     input
 }
 
-
-#[derive(Clone)]
-struct Large {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<i32>,
-    e: HashMap<String, i32>,
-    f: bool,
-    g: Option<i32>,
-    h: Vec<String>,
-    i: f64,
-    j: HashMap<String, i32>,
-
-    k: String,
-    l: Vec<u8>,
-    m: Option<String>,
-    n: HashMap<String, String>,
-    o: bool,
-
-    p: Vec<i64>,
-    q: Option<HashMap<String, i32>>,
-    r: Vec<String>,
-    s: bool,
-    t: f64,
-}
-
-#[derive(Clone)]
-struct LargeV2 {
-    a: i32,
-    b: String,
-    c: f64,
-    d: Vec<i32>,
-    e: HashMap<String, i32>,
-    f: bool,
-    g: Option<i32>,
-    h: Vec<String>,
-    i: f64,
-    j: HashMap<String, i32>,
-
-    k: String,
-    l: Vec<u8>,
-    m: Option<String>,
-    n: HashMap<String, String>,
-    o: bool,
-
-    p: Vec<i64>,
-    q: Option<HashMap<String, i32>>,
-    r: Vec<String>,
-    s: bool,
-    t: f64,
-}
-
 // Map and return mapped struct for Large
 fn map_large(input: Large) -> LargeV2 {
     LargeV2 {
         a: input.a + 1,
-        b: input.b,
-        c: input.c + 1.0,
+        b: input.b + 1.0,
+        c: input.c,
         d: input.d,
         e: input.e,
-        f: input.f,
-        g: input.g.map(|v| v + 1),
+        f: input.f.map(|v| v + 1),
+        g: input.g + 1,
         h: input.h,
-        i: input.i + 1.0,
-        j: input.j,
-
-        k: input.k,
+        i: input.i,
+        j: input.j + 1.0,
+        k: input.k + 1,
         l: input.l,
         m: input.m,
         n: input.n,
         o: input.o,
-
         p: input.p,
         q: input.q,
         r: input.r,
         s: input.s,
-        t: input.t + 1.0,
+        t: input.t,
+        u: input.u + 1.0,
+        v: input.v,
+        w: input.w + 1,
+        x: input.x + 1.0,
+        y: input.y + 1,
+        z: input.z,
+        aa: input.aa,
+        ab: input.ab + 1,
+        ac: input.ac,
+        ad: input.ad,
+        ae: input.ae,
+        af: input.af,
     }
 }
 
@@ -204,22 +98,8 @@ fn return_large(input: Large) -> Large {
     input
 }
 
-
 fn bench_small(c: &mut Criterion) {
-    let mut small_map = HashMap::new();
-    small_map.insert("key".to_string(), 42);
-    let small = Small {
-        a: 1,
-        b: "small".into(),
-        c: 1.0,
-        d: vec![1, 2, 3],
-        e: small_map.clone(),
-        f: true,
-        g: Some(10),
-        h: vec!["hello".into()],
-        i: 3.14,
-        j: small_map,
-    };
+    let small = Small::build(BENCH_SIZE);
 
     c.bench_function("small_map_and_return", |b| {
         b.iter(|| {
@@ -239,30 +119,7 @@ fn bench_small(c: &mut Criterion) {
 }
 
 fn bench_medium(c: &mut Criterion) {
-    let mut map_i32 = HashMap::new();
-    map_i32.insert("key".to_string(), 42);
-
-    let mut map_str = HashMap::new();
-    map_str.insert("hello".to_string(), "world".to_string());
-
-    let medium = Medium {
-        a: 1,
-        b: "medium".into(),
-        c: 2.0,
-        d: vec![4, 5, 6],
-        e: map_i32.clone(),
-        f: false,
-        g: Some(20),
-        h: vec!["world".into()],
-        i: 6.28,
-        j: map_i32.clone(),
-
-        k: "extra".into(),
-        l: vec![1, 2, 3, 4],
-        m: Some("option".into()),
-        n: map_str,
-        o: true,
-    };
+    let medium = Medium::build(BENCH_SIZE);
 
     c.bench_function("medium_map_and_return", |b| {
         b.iter(|| {
@@ -282,36 +139,7 @@ fn bench_medium(c: &mut Criterion) {
 }
 
 fn bench_large(c: &mut Criterion) {
-    let mut map_i32 = HashMap::new();
-    map_i32.insert("key".to_string(), 42);
-
-    let mut map_str = HashMap::new();
-    map_str.insert("hello".to_string(), "world".to_string());
-
-    let large = Large {
-        a: 1,
-        b: "large".into(),
-        c: 3.0,
-        d: vec![7, 8, 9],
-        e: map_i32.clone(),
-        f: true,
-        g: Some(30),
-        h: vec!["large".into()],
-        i: 9.42,
-        j: map_i32.clone(),
-
-        k: "bigger".into(),
-        l: vec![5, 6, 7, 8],
-        m: Some("some option".into()),
-        n: map_str.clone(),
-        o: false,
-
-        p: vec![10, 20, 30],
-        q: Some(map_i32),
-        r: vec!["string1".into(), "string2".into()],
-        s: true,
-        t: 42.0,
-    };
+    let large = Large::build(BENCH_SIZE);
 
     c.bench_function("large_map_and_return", |b| {
         b.iter(|| {
@@ -331,4 +159,4 @@ fn bench_large(c: &mut Criterion) {
 }
 
 criterion::criterion_group!(benches, bench_small, bench_medium, bench_large);
-criterion::criterion_main!(benches);
+rust_examples::bench_main!(benches);
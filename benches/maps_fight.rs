@@ -1,6 +1,11 @@
-use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use criterion::measurement::WallTime;
+use criterion::{
+    BatchSize, BenchmarkGroup, BenchmarkId, Criterion, Throughput, black_box, criterion_group,
+};
+use rust_examples::hashers::{self, Key};
 use small_map::SmallMap;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 use tinymap::ArrayMap;
 
 // How many entries SmallMap keeps inline before spilling.
@@ -10,14 +15,14 @@ const SMALLMAP_INLINE: usize = 128;
 // Make & fill (alloc + insert are timed)
 // -----------------------------
 
-fn make_and_fill_hashmap_prealloc<const N: usize>() {
+fn make_and_fill_hashmap_prealloc<const N: usize, K: Key, S: BuildHasher + Default>() {
     // Pre-allocate to avoid rehash growth noise.
-    let mut m: HashMap<u32, u32> = HashMap::with_capacity(N);
-    for i in 0..N as u32 {
-        m.insert(black_box(i), black_box(i));
+    let mut m: HashMap<K, u32, S> = HashMap::with_capacity_and_hasher(N, S::default());
+    for i in 0..N as u64 {
+        m.insert(black_box(K::nth(i)), black_box(i as u32));
     }
     black_box(m.len());
-    black_box(m.get(&0));
+    black_box(m.get(&K::nth(0)));
 }
 
 fn make_and_fill_smallmap_inline_then_spill<const N: usize>() {
@@ -45,18 +50,35 @@ fn make_and_fill_arraymap_stack<const N: usize>() {
 // Bench harnesses
 // -----------------------------
 
+// `HashMap` with each hasher from `rust_examples::hashers`, so the small
+// maps are not only compared against SipHash. The SipHash id predates the
+// matrix and stays as it was.
+struct HashMapFill<'g, 'a, const N: usize> {
+    group: &'g mut BenchmarkGroup<'a, WallTime>,
+}
+
+impl<const N: usize> hashers::Visitor for HashMapFill<'_, '_, N> {
+    fn visit<K: Key, S: BuildHasher + Default + Clone>(&mut self, hasher: &'static str) {
+        let id = if hasher == hashers::DEFAULT {
+            "HashMap::with_capacity(N) + insert N".to_string()
+        } else {
+            format!("HashMap<{hasher}>::with_capacity(N) + insert N")
+        };
+        self.group
+            .bench_function(id, |b| b.iter(make_and_fill_hashmap_prealloc::<N, K, S>));
+    }
+}
+
 fn bench_make_and_fill_small_ns<const N: usize>(c: &mut Criterion) {
     let mut g = c.benchmark_group(format!("map_make_and_fill_small_N={}", N));
     g.throughput(Throughput::Elements(N as u64));
 
-    g.bench_function("HashMap::with_capacity(N) + insert N", |b| {
-        b.iter(|| make_and_fill_hashmap_prealloc::<N>())
-    });
+    hashers::visit_key(u32::NAME, &mut HashMapFill::<N> { group: &mut g });
     g.bench_function("SmallMap::new() + insert N", |b| {
-        b.iter(|| make_and_fill_smallmap_inline_then_spill::<N>())
+        b.iter(make_and_fill_smallmap_inline_then_spill::<N>)
     });
     g.bench_function("ArrayMap::new() + insert N (stack, fixed)", |b| {
-        b.iter(|| make_and_fill_arraymap_stack::<N>())
+        b.iter(make_and_fill_arraymap_stack::<N>)
     });
 
     g.finish();
@@ -66,11 +88,9 @@ fn bench_make_and_fill_large_ns<const N: usize>(c: &mut Criterion) {
     let mut g = c.benchmark_group(format!("map_make_and_fill_large_N={}", N));
     g.throughput(Throughput::Elements(N as u64));
 
-    g.bench_function("HashMap::with_capacity(N) + insert N", |b| {
-        b.iter(|| make_and_fill_hashmap_prealloc::<N>())
-    });
+    hashers::visit_key(u32::NAME, &mut HashMapFill::<N> { group: &mut g });
     g.bench_function("SmallMap::new() + insert N (may spill)", |b| {
-        b.iter(|| make_and_fill_smallmap_inline_then_spill::<N>())
+        b.iter(make_and_fill_smallmap_inline_then_spill::<N>)
     });
 
     g.finish();
@@ -96,5 +116,49 @@ fn benches(c: &mut Criterion) {
     bench_make_and_fill_large_ns::<1_000_000>(c);
 }
 
-criterion_group!(benches_group, benches);
-criterion_main!(benches_group);
+// -----------------------------
+// Hasher matrix (see `rust_examples::hashers`)
+// -----------------------------
+
+const HASHER_NS: [usize; 2] = [1_000, 100_000];
+
+// One group per key type; the function is the hasher and the parameter N.
+struct FillMatrix<'g, 'a> {
+    group: &'g mut BenchmarkGroup<'a, WallTime>,
+}
+
+impl hashers::Visitor for FillMatrix<'_, '_> {
+    fn visit<K: Key, S: BuildHasher + Default + Clone>(&mut self, hasher: &'static str) {
+        for n in HASHER_NS {
+            let keys: Vec<K> = (0..n as u64).map(K::nth).collect();
+            self.group.throughput(Throughput::Elements(n as u64));
+            // Keys are cloned in setup and the map dropped afterwards, so
+            // only hashing and inserting are timed.
+            self.group.bench_function(BenchmarkId::new(hasher, n), |b| {
+                b.iter_batched(
+                    || keys.clone(),
+                    |keys| {
+                        let mut m: HashMap<K, u32, S> =
+                            HashMap::with_capacity_and_hasher(keys.len(), S::default());
+                        for (i, k) in keys.into_iter().enumerate() {
+                            m.insert(k, i as u32);
+                        }
+                        m
+                    },
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+}
+
+fn bench_hasher_matrix(c: &mut Criterion) {
+    for &key in hashers::KEYS {
+        let mut group = c.benchmark_group(format!("map_hasher_fill_{key}"));
+        hashers::visit_key(key, &mut FillMatrix { group: &mut group });
+        group.finish();
+    }
+}
+
+criterion_group!(benches_group, benches, bench_hasher_matrix);
+rust_examples::bench_main!(benches_group);
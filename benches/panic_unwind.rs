@@ -0,0 +1,149 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group};
+use std::panic::{self, AssertUnwindSafe};
+
+// Calls per measured iteration.
+const CALLS: u64 = 10_000;
+
+// Cargo ignores `panic = "abort"` for bench targets, so the `bench-abort`
+// profile alone still unwinds. To time the abort build, set the flag on the
+// compiler directly:
+//
+//     RUSTFLAGS="-C panic=abort" cargo bench --profile bench-abort --bench panic_unwind
+//
+// and `run-suite size panic_unwind` for the binary-size side. Under abort
+// `catch_unwind` cannot catch anything, so only the non-panicking groups run.
+
+// ---------------------------------------
+// Work and frames
+// ---------------------------------------
+
+#[inline(never)]
+fn work(id: u64) -> u64 {
+    id.wrapping_mul(31) ^ 7
+}
+
+#[inline(never)]
+fn work_result(id: u64) -> Result<u64, u64> {
+    if black_box(id) == u64::MAX {
+        Err(id)
+    } else {
+        Ok(work(id))
+    }
+}
+
+// Every frame owns something with a destructor, so unwinding has cleanup
+// to run and the `Err` path has drops to perform on the way out.
+struct Guard(u64);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        black_box(self.0);
+    }
+}
+
+#[inline(never)]
+fn descend_panic(depth: u32, id: u64) -> u64 {
+    let _guard = Guard(id);
+    if depth == 0 {
+        panic!("user {id} not found");
+    }
+    // `+ 1` keeps the recursion from becoming a tail call.
+    descend_panic(depth - 1, id) + 1
+}
+
+#[inline(never)]
+fn descend_err(depth: u32, id: u64) -> Result<u64, String> {
+    let _guard = Guard(id);
+    if depth == 0 {
+        return Err(format!("user {id} not found"));
+    }
+    Ok(descend_err(depth - 1, id)? + 1)
+}
+
+// ---------------------------------------
+// Benches
+// ---------------------------------------
+
+// A `catch_unwind` boundary that never fires, as around every FFI entry.
+fn bench_happy_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("unwind_happy_path");
+
+    group.bench_function("direct", |b| {
+        b.iter(|| {
+            let mut sum = 0u64;
+            for id in 0..CALLS {
+                sum = sum.wrapping_add(work(black_box(id)));
+            }
+            sum
+        })
+    });
+
+    group.bench_function("result", |b| {
+        b.iter(|| {
+            let mut sum = 0u64;
+            for id in 0..CALLS {
+                if let Ok(v) = work_result(black_box(id)) {
+                    sum = sum.wrapping_add(v);
+                }
+            }
+            sum
+        })
+    });
+
+    group.bench_function("catch_unwind", |b| {
+        b.iter(|| {
+            let mut sum = 0u64;
+            for id in 0..CALLS {
+                if let Ok(v) = panic::catch_unwind(|| work(black_box(id))) {
+                    sum = sum.wrapping_add(v);
+                }
+            }
+            sum
+        })
+    });
+
+    group.finish();
+}
+
+// Failing at the bottom of `depth` frames: a panic caught at the top
+// against an `Err` returned through the same frames.
+fn bench_failure_depth(c: &mut Criterion) {
+    let mut group = c.benchmark_group("unwind_depth");
+
+    // The default hook would print every panic; the message is still built.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    for depth in [1, 4, 16, 64] {
+        group.bench_function(BenchmarkId::new("err", depth), |b| {
+            b.iter(|| {
+                let mut failed = 0u64;
+                for id in 0..CALLS / 10 {
+                    failed += descend_err(depth, black_box(id)).is_err() as u64;
+                }
+                failed
+            })
+        });
+
+        if cfg!(panic = "unwind") {
+            group.bench_function(BenchmarkId::new("panic", depth), |b| {
+                b.iter(|| {
+                    let mut failed = 0u64;
+                    for id in 0..CALLS / 10 {
+                        let caught = panic::catch_unwind(AssertUnwindSafe(|| {
+                            descend_panic(depth, black_box(id))
+                        }));
+                        failed += caught.is_err() as u64;
+                    }
+                    failed
+                })
+            });
+        }
+    }
+
+    panic::set_hook(hook);
+    group.finish();
+}
+
+criterion_group!(benches, bench_happy_path, bench_failure_depth);
+rust_examples::bench_main!(benches);
@@ -0,0 +1,156 @@
+use criterion::{BatchSize, Criterion, black_box, criterion_group};
+use rust_examples::service::{
+    self, CreateUserRequest, FileUserRepo, InMemoryUserRepo, UserController, UserId, UserRepo,
+};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
+
+// Users seeded before each group; reads cycle through all of them.
+const USERS: usize = 1_000;
+
+// ---------------------------------------
+// Wiring strategies
+// ---------------------------------------
+//
+// move    : the controller owns the stack; data moves across every layer
+// clone   : same stack, but each layer maps by reference and clones
+// arc     : the repo is shared; a controller is wired per request around
+//           an `Arc` clone, as request-scoped handlers do
+// box_dyn : the controller owns a `Box<dyn UserRepo>`; every repo call
+//           goes through the vtable
+
+fn request(i: usize) -> CreateUserRequest {
+    CreateUserRequest {
+        email: format!("new{i}@example.com"),
+        name: format!("New User {i}"),
+        tags: vec!["fresh".to_string(), "bench".to_string()],
+    }
+}
+
+fn next(ids: &[UserId], cursor: &mut usize) -> u64 {
+    *cursor = (*cursor + 1) % ids.len();
+    black_box(ids[*cursor].0)
+}
+
+// GET /users/{id} end to end: repo lookup, entity -> domain -> response.
+fn bench_get<R: UserRepo + 'static>(c: &mut Criterion, backend: &str, mut open: impl FnMut() -> R) {
+    let mut group = c.benchmark_group(format!("service_get_{backend}"));
+
+    let repo = open();
+    let ids = service::seed(&repo, USERS).unwrap();
+    let controller = UserController::wire(repo);
+    let mut cursor = 0;
+    group.bench_function("move", |b| {
+        b.iter(|| controller.get(next(&ids, &mut cursor)).unwrap())
+    });
+    group.bench_function("clone", |b| {
+        b.iter(|| controller.get_cloned(next(&ids, &mut cursor)).unwrap())
+    });
+
+    let shared = Arc::new(open());
+    let ids = service::seed(&shared, USERS).unwrap();
+    group.bench_function("arc", |b| {
+        b.iter(|| {
+            UserController::wire(Arc::clone(&shared))
+                .get(next(&ids, &mut cursor))
+                .unwrap()
+        })
+    });
+
+    let boxed: Box<dyn UserRepo> = Box::new(open());
+    let ids = service::seed(&boxed, USERS).unwrap();
+    let controller = UserController::wire(boxed);
+    group.bench_function("box_dyn", |b| {
+        b.iter(|| controller.get(next(&ids, &mut cursor)).unwrap())
+    });
+
+    group.finish();
+}
+
+// POST then DELETE, so the store stays at `USERS` entries however many
+// iterations Criterion runs. In memory only: the file store is append-only
+// and would grow by a record and a tombstone per iteration.
+fn bench_create(c: &mut Criterion) {
+    let mut group = c.benchmark_group("service_create_memory");
+    let input = request(0);
+
+    let controller = UserController::wire(InMemoryUserRepo::new());
+    service::seed(controller.use_case().repo(), USERS).unwrap();
+    group.bench_function("move", |b| {
+        b.iter_batched(
+            || input.clone(),
+            |req| {
+                let created = controller.create(req).unwrap();
+                controller.delete(created.id).unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("clone", |b| {
+        b.iter_batched(
+            || input.clone(),
+            |req| {
+                let created = controller.create_cloned(&req).unwrap();
+                controller.delete(created.id).unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    let shared = Arc::new(InMemoryUserRepo::new());
+    service::seed(&shared, USERS).unwrap();
+    group.bench_function("arc", |b| {
+        b.iter_batched(
+            || input.clone(),
+            |req| {
+                let controller = UserController::wire(Arc::clone(&shared));
+                let created = controller.create(req).unwrap();
+                controller.delete(created.id).unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    let boxed: Box<dyn UserRepo> = Box::new(InMemoryUserRepo::new());
+    service::seed(&boxed, USERS).unwrap();
+    let controller = UserController::wire(boxed);
+    group.bench_function("box_dyn", |b| {
+        b.iter_batched(
+            || input.clone(),
+            |req| {
+                let created = controller.create(req).unwrap();
+                controller.delete(created.id).unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn bench_memory(c: &mut Criterion) {
+    bench_get(c, "memory", InMemoryUserRepo::new);
+    bench_create(c);
+}
+
+// Each strategy gets its own store file; the page cache keeps reads warm,
+// so this measures syscalls and decoding rather than the disk.
+fn bench_file(c: &mut Criterion) {
+    let dir = env::temp_dir().join(format!("service_layers-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let mut opened = 0;
+    let path = |n: usize| -> PathBuf { dir.join(format!("users-{n}.tsv")) };
+
+    bench_get(c, "file", || {
+        opened += 1;
+        FileUserRepo::open(&path(opened)).unwrap()
+    });
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+criterion_group!(benches, bench_memory, bench_file);
+rust_examples::bench_main!(benches);
@@ -0,0 +1,391 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group};
+use rust_examples::data::Gen;
+
+// Elements per kernel call: fits L1, then spills past L2.
+const SIZES: [usize; 2] = [1_024, 65_536];
+
+// `native` when the whole target was compiled with AVX2, which is what
+// `-C target-cpu=native` gives on any AVX2 machine:
+//
+//   cargo run --bin run-suite -- run simd --native
+//
+// The suffix keeps the two builds' results apart.
+const BUILD: &str = if cfg!(target_feature = "avx2") {
+    "native"
+} else {
+    "base"
+};
+
+// Every kernel is its own out-of-line function in `kernels`, so
+// `run-suite asm simd` and `run-suite report` can tell which ones LLVM
+// vectorised. Each comes in five forms: an indexed loop, the iterator
+// form, eight accumulators over `chunks_exact(8)`, and SSE2 and AVX2
+// intrinsics. The dot products multiply the two halves of their input.
+mod kernels {
+    #![allow(clippy::needless_range_loop)]
+
+    // -----------------------------
+    // Integer sum: reassociation is free, so the scalar forms vectorise.
+    // -----------------------------
+
+    #[inline(never)]
+    pub fn sum_u32_scalar(v: &[u32]) -> u32 {
+        let mut sum = 0u32;
+        for i in 0..v.len() {
+            sum = sum.wrapping_add(v[i]);
+        }
+        sum
+    }
+
+    #[inline(never)]
+    pub fn sum_u32_iterator(v: &[u32]) -> u32 {
+        v.iter().fold(0u32, |acc, &x| acc.wrapping_add(x))
+    }
+
+    #[inline(never)]
+    pub fn sum_u32_chunks_exact(v: &[u32]) -> u32 {
+        let mut acc = [0u32; 8];
+        let chunks = v.chunks_exact(8);
+        let tail = chunks.remainder();
+        for c in chunks {
+            for j in 0..8 {
+                acc[j] = acc[j].wrapping_add(c[j]);
+            }
+        }
+        tail.iter()
+            .chain(&acc)
+            .fold(0u32, |acc, &x| acc.wrapping_add(x))
+    }
+
+    // -----------------------------
+    // Float sum: the scalar forms must add in order, so they stay scalar.
+    // -----------------------------
+
+    #[inline(never)]
+    pub fn sum_f32_scalar(v: &[f32]) -> f32 {
+        let mut sum = 0.0;
+        for i in 0..v.len() {
+            sum += v[i];
+        }
+        sum
+    }
+
+    #[inline(never)]
+    pub fn sum_f32_iterator(v: &[f32]) -> f32 {
+        v.iter().sum()
+    }
+
+    #[inline(never)]
+    pub fn sum_f32_chunks_exact(v: &[f32]) -> f32 {
+        let mut acc = [0.0f32; 8];
+        let chunks = v.chunks_exact(8);
+        let tail = chunks.remainder();
+        for c in chunks {
+            for j in 0..8 {
+                acc[j] += c[j];
+            }
+        }
+        acc.iter().sum::<f32>() + tail.iter().sum::<f32>()
+    }
+
+    // -----------------------------
+    // Float dot product
+    // -----------------------------
+
+    #[inline(never)]
+    pub fn dot_f32_scalar(v: &[f32]) -> f32 {
+        let (a, b) = v.split_at(v.len() / 2);
+        let mut sum = 0.0;
+        for i in 0..a.len().min(b.len()) {
+            sum += a[i] * b[i];
+        }
+        sum
+    }
+
+    #[inline(never)]
+    pub fn dot_f32_iterator(v: &[f32]) -> f32 {
+        let (a, b) = v.split_at(v.len() / 2);
+        a.iter().zip(b).map(|(x, y)| x * y).sum()
+    }
+
+    #[inline(never)]
+    pub fn dot_f32_chunks_exact(v: &[f32]) -> f32 {
+        let (a, b) = v.split_at(v.len() / 2);
+        let b = &b[..a.len()];
+        let mut acc = [0.0f32; 8];
+        let (ca, cb) = (a.chunks_exact(8), b.chunks_exact(8));
+        let (ta, tb) = (ca.remainder(), cb.remainder());
+        for (x, y) in ca.zip(cb) {
+            for j in 0..8 {
+                acc[j] += x[j] * y[j];
+            }
+        }
+        acc.iter().sum::<f32>() + ta.iter().zip(tb).map(|(x, y)| x * y).sum::<f32>()
+    }
+
+    // -----------------------------
+    // Intrinsics
+    // -----------------------------
+
+    #[cfg(target_arch = "x86_64")]
+    pub use self::x86::*;
+
+    // Unaligned loads throughout; the tails are summed in scalar code.
+    #[cfg(target_arch = "x86_64")]
+    mod x86 {
+        use std::arch::x86_64::*;
+
+        // SSE2 is part of x86_64, so these need no runtime check.
+        #[inline(never)]
+        pub fn sum_u32_sse2(v: &[u32]) -> u32 {
+            let chunks = v.chunks_exact(4);
+            let tail = chunks.remainder();
+            // SAFETY: each chunk is four readable u32s; SSE2 is baseline.
+            let lanes: [u32; 4] = unsafe {
+                let mut acc = _mm_setzero_si128();
+                for c in chunks {
+                    acc = _mm_add_epi32(acc, _mm_loadu_si128(c.as_ptr().cast()));
+                }
+                std::mem::transmute(acc)
+            };
+            lanes
+                .iter()
+                .chain(tail)
+                .fold(0u32, |acc, &x| acc.wrapping_add(x))
+        }
+
+        #[inline(never)]
+        pub fn sum_f32_sse2(v: &[f32]) -> f32 {
+            let chunks = v.chunks_exact(4);
+            let tail = chunks.remainder();
+            // SAFETY: each chunk is four readable f32s; SSE2 is baseline.
+            let lanes: [f32; 4] = unsafe {
+                let mut acc = _mm_setzero_ps();
+                for c in chunks {
+                    acc = _mm_add_ps(acc, _mm_loadu_ps(c.as_ptr()));
+                }
+                std::mem::transmute(acc)
+            };
+            lanes.iter().sum::<f32>() + tail.iter().sum::<f32>()
+        }
+
+        #[inline(never)]
+        pub fn dot_f32_sse2(v: &[f32]) -> f32 {
+            let (a, b) = v.split_at(v.len() / 2);
+            let b = &b[..a.len()];
+            let (ca, cb) = (a.chunks_exact(4), b.chunks_exact(4));
+            let (ta, tb) = (ca.remainder(), cb.remainder());
+            // SAFETY: each chunk is four readable f32s; SSE2 is baseline.
+            let lanes: [f32; 4] = unsafe {
+                let mut acc = _mm_setzero_ps();
+                for (x, y) in ca.zip(cb) {
+                    let p = _mm_mul_ps(_mm_loadu_ps(x.as_ptr()), _mm_loadu_ps(y.as_ptr()));
+                    acc = _mm_add_ps(acc, p);
+                }
+                std::mem::transmute(acc)
+            };
+            lanes.iter().sum::<f32>() + ta.iter().zip(tb).map(|(x, y)| x * y).sum::<f32>()
+        }
+
+        /// # Safety
+        ///
+        /// The CPU must support AVX2.
+        #[inline(never)]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn sum_u32_avx2(v: &[u32]) -> u32 {
+            let chunks = v.chunks_exact(8);
+            let tail = chunks.remainder();
+            // SAFETY: each chunk is eight readable u32s.
+            let lanes: [u32; 8] = unsafe {
+                let mut acc = _mm256_setzero_si256();
+                for c in chunks {
+                    acc = _mm256_add_epi32(acc, _mm256_loadu_si256(c.as_ptr().cast()));
+                }
+                std::mem::transmute(acc)
+            };
+            lanes
+                .iter()
+                .chain(tail)
+                .fold(0u32, |acc, &x| acc.wrapping_add(x))
+        }
+
+        /// # Safety
+        ///
+        /// The CPU must support AVX2.
+        #[inline(never)]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn sum_f32_avx2(v: &[f32]) -> f32 {
+            let chunks = v.chunks_exact(8);
+            let tail = chunks.remainder();
+            // SAFETY: each chunk is eight readable f32s.
+            let lanes: [f32; 8] = unsafe {
+                let mut acc = _mm256_setzero_ps();
+                for c in chunks {
+                    acc = _mm256_add_ps(acc, _mm256_loadu_ps(c.as_ptr()));
+                }
+                std::mem::transmute(acc)
+            };
+            lanes.iter().sum::<f32>() + tail.iter().sum::<f32>()
+        }
+
+        /// # Safety
+        ///
+        /// The CPU must support AVX2 and FMA.
+        #[inline(never)]
+        #[target_feature(enable = "avx2,fma")]
+        pub unsafe fn dot_f32_avx2(v: &[f32]) -> f32 {
+            let (a, b) = v.split_at(v.len() / 2);
+            let b = &b[..a.len()];
+            let (ca, cb) = (a.chunks_exact(8), b.chunks_exact(8));
+            let (ta, tb) = (ca.remainder(), cb.remainder());
+            // SAFETY: each chunk is eight readable f32s.
+            let lanes: [f32; 8] = unsafe {
+                let mut acc = _mm256_setzero_ps();
+                for (x, y) in ca.zip(cb) {
+                    acc = _mm256_fmadd_ps(
+                        _mm256_loadu_ps(x.as_ptr()),
+                        _mm256_loadu_ps(y.as_ptr()),
+                        acc,
+                    );
+                }
+                std::mem::transmute(acc)
+            };
+            lanes.iter().sum::<f32>() + ta.iter().zip(tb).map(|(x, y)| x * y).sum::<f32>()
+        }
+    }
+}
+
+// -----------------------------
+// Runtime dispatch
+// -----------------------------
+
+type Kernel<T> = (&'static str, fn(&[T]) -> T);
+
+fn has_avx2() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        is_x86_feature_detected!("avx2")
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        false
+    }
+}
+
+// Only the dot product uses FMA; the sums run on AVX2 alone.
+fn has_avx2_fma() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        has_avx2() && is_x86_feature_detected!("fma")
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        false
+    }
+}
+
+fn sum_u32_kernels() -> Vec<Kernel<u32>> {
+    let mut out: Vec<Kernel<u32>> = vec![
+        ("scalar", kernels::sum_u32_scalar),
+        ("iterator", kernels::sum_u32_iterator),
+        ("chunks_exact", kernels::sum_u32_chunks_exact),
+    ];
+    #[cfg(target_arch = "x86_64")]
+    {
+        out.push(("sse2", kernels::sum_u32_sse2));
+        if has_avx2() {
+            // SAFETY: only registered once AVX2 was detected.
+            out.push(("avx2", |v| unsafe { kernels::sum_u32_avx2(v) }));
+        }
+    }
+    out
+}
+
+fn sum_f32_kernels() -> Vec<Kernel<f32>> {
+    let mut out: Vec<Kernel<f32>> = vec![
+        ("scalar", kernels::sum_f32_scalar),
+        ("iterator", kernels::sum_f32_iterator),
+        ("chunks_exact", kernels::sum_f32_chunks_exact),
+    ];
+    #[cfg(target_arch = "x86_64")]
+    {
+        out.push(("sse2", kernels::sum_f32_sse2));
+        if has_avx2() {
+            // SAFETY: only registered once AVX2 was detected.
+            out.push(("avx2", |v| unsafe { kernels::sum_f32_avx2(v) }));
+        }
+    }
+    out
+}
+
+fn dot_f32_kernels() -> Vec<Kernel<f32>> {
+    let mut out: Vec<Kernel<f32>> = vec![
+        ("scalar", kernels::dot_f32_scalar),
+        ("iterator", kernels::dot_f32_iterator),
+        ("chunks_exact", kernels::dot_f32_chunks_exact),
+    ];
+    #[cfg(target_arch = "x86_64")]
+    {
+        out.push(("sse2", kernels::dot_f32_sse2));
+        if has_avx2_fma() {
+            // SAFETY: only registered once AVX2 and FMA were detected.
+            out.push(("avx2", |v| unsafe { kernels::dot_f32_avx2(v) }));
+        }
+    }
+    out
+}
+
+// -----------------------------
+// Benches
+// -----------------------------
+
+// Every form must agree with the scalar loop before anything is timed;
+// float forms add in a different order, so only to a relative tolerance.
+fn check<T: Copy + std::fmt::Debug>(
+    group: &str,
+    input: &[T],
+    kernels: &[Kernel<T>],
+    close: fn(T, T) -> bool,
+) {
+    let expected = (kernels[0].1)(input);
+    for &(name, f) in kernels {
+        let got = f(input);
+        assert!(
+            close(got, expected),
+            "{group}/{name}: {got:?}, scalar gave {expected:?}"
+        );
+    }
+}
+
+fn bench_kernel<T: Copy + std::fmt::Debug>(
+    c: &mut Criterion,
+    kernel: &str,
+    input: &[T],
+    kernels: &[Kernel<T>],
+    close: fn(T, T) -> bool,
+) {
+    let group = format!("simd_{kernel}_{}_{BUILD}", input.len());
+    check(&group, input, kernels, close);
+
+    let mut g = c.benchmark_group(group);
+    g.throughput(Throughput::Elements(input.len() as u64));
+    for &(name, f) in kernels {
+        g.bench_function(name, |b| b.iter(|| f(black_box(input))));
+    }
+    g.finish();
+}
+
+fn bench_simd(c: &mut Criterion) {
+    let exact = |a: u32, b: u32| a == b;
+    let close = |a: f32, b: f32| (a - b).abs() <= 1e-3 * b.abs().max(1.0);
+    for n in SIZES {
+        let ints: Vec<u32> = Gen::new(&format!("simd/u32/{n}")).uniform(n, 0..1_000);
+        let floats: Vec<f32> = Gen::new(&format!("simd/f32/{n}")).uniform(n, 0.0..1.0);
+        bench_kernel(c, "sum_u32", &ints, &sum_u32_kernels(), exact);
+        bench_kernel(c, "sum_f32", &floats, &sum_f32_kernels(), close);
+        bench_kernel(c, "dot_f32", &floats, &dot_f32_kernels(), close);
+    }
+}
+
+criterion_group!(benches, bench_simd);
+rust_examples::bench_main!(benches);
@@ -0,0 +1,313 @@
+use criterion::criterion_group;
+use criterion::measurement::WallTime;
+use criterion::{BatchSize, BenchmarkGroup, BenchmarkId, Criterion, Throughput, black_box};
+use rust_examples::data::Gen;
+
+// Elements per sort.
+const N: usize = 10_000;
+// Distinct keys in the few-unique distribution.
+const FEW_UNIQUE: u64 = 16;
+
+// Sorted slice lengths for the searches: a cache line or two, L1, past L2.
+const SEARCH_SIZES: [usize; 3] = [16, 1_024, 65_536];
+// Queries per measured iteration.
+const PROBES: usize = 1_000;
+
+// -----------------------------
+// Element types
+// -----------------------------
+
+/// An element type under test, built from and ordered like a `u64` key.
+trait Elem: Ord + Clone + 'static {
+    const NAME: &'static str;
+    /// Largest key [`Elem::from_key`] represents without loss.
+    const MAX: u64;
+    /// Key bytes for [`radix_sort`]; `None` where a radix sort does not apply.
+    const RADIX_BYTES: Option<usize>;
+
+    fn from_key(key: u64) -> Self;
+    /// The key back, for `sort_by_key`; free for integers, a parse for strings.
+    fn key(&self) -> u64;
+}
+
+impl Elem for u32 {
+    const NAME: &'static str = "u32";
+    const MAX: u64 = u32::MAX as u64;
+    const RADIX_BYTES: Option<usize> = Some(4);
+
+    fn from_key(key: u64) -> u32 {
+        key as u32
+    }
+    fn key(&self) -> u64 {
+        u64::from(*self)
+    }
+}
+
+impl Elem for u64 {
+    const NAME: &'static str = "u64";
+    const MAX: u64 = u64::MAX;
+    const RADIX_BYTES: Option<usize> = Some(8);
+
+    fn from_key(key: u64) -> u64 {
+        key
+    }
+    fn key(&self) -> u64 {
+        *self
+    }
+}
+
+// Zero-padded hex, so string order matches key order.
+impl Elem for String {
+    const NAME: &'static str = "string";
+    const MAX: u64 = u64::MAX;
+    const RADIX_BYTES: Option<usize> = None;
+
+    fn from_key(key: u64) -> String {
+        format!("{key:016x}")
+    }
+    fn key(&self) -> u64 {
+        u64::from_str_radix(self, 16).unwrap()
+    }
+}
+
+/// A 64-byte row ordered by its key field, so moves cost a cache line.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Record {
+    key: u64,
+    payload: [u64; 7],
+}
+
+impl Elem for Record {
+    const NAME: &'static str = "record64";
+    const MAX: u64 = u64::MAX;
+    const RADIX_BYTES: Option<usize> = Some(8);
+
+    fn from_key(key: u64) -> Record {
+        Record {
+            key,
+            payload: [key; 7],
+        }
+    }
+    fn key(&self) -> u64 {
+        self.key
+    }
+}
+
+// -----------------------------
+// Radix sort and Eytzinger layout
+// -----------------------------
+
+/// LSD radix sort on [`Elem::key`], one byte per pass, stable. A pass where
+/// every element has the same byte is skipped, so narrow keys stay cheap.
+fn radix_sort<T: Elem>(v: &mut Vec<T>, bytes: usize) {
+    if v.len() < 2 {
+        return;
+    }
+    let mut scratch = v.clone();
+    for shift in (0..bytes).map(|b| 8 * b) {
+        let mut counts = [0usize; 256];
+        for x in v.iter() {
+            counts[(x.key() >> shift) as usize & 0xff] += 1;
+        }
+        if counts.contains(&v.len()) {
+            continue;
+        }
+        let mut offsets = [0usize; 256];
+        for b in 1..256 {
+            offsets[b] = offsets[b - 1] + counts[b - 1];
+        }
+        for x in v.iter() {
+            let b = (x.key() >> shift) as usize & 0xff;
+            scratch[offsets[b]] = x.clone();
+            offsets[b] += 1;
+        }
+        std::mem::swap(v, &mut scratch);
+    }
+}
+
+/// A sorted slice stored in breadth-first (Eytzinger) order: node `k` has
+/// children `2k` and `2k + 1` (1-based), so the first levels of every search
+/// share a few cache lines and the loop has no data-dependent branch.
+struct Eytzinger<T>(Vec<T>);
+
+impl<T: Ord + Clone> Eytzinger<T> {
+    fn new(sorted: &[T]) -> Eytzinger<T> {
+        fn fill<T: Clone>(sorted: &[T], out: &mut Vec<Option<T>>, k: usize, next: &mut usize) {
+            if k <= sorted.len() {
+                fill(sorted, out, 2 * k, next);
+                out[k - 1] = Some(sorted[*next].clone());
+                *next += 1;
+                fill(sorted, out, 2 * k + 1, next);
+            }
+        }
+        let mut out = vec![None; sorted.len()];
+        fill(sorted, &mut out, 1, &mut 0);
+        Eytzinger(out.into_iter().map(Option::unwrap).collect())
+    }
+
+    /// Whether `x` is present.
+    fn contains(&self, x: &T) -> bool {
+        let n = self.0.len();
+        let mut k = 1;
+        while k <= n {
+            k = 2 * k + usize::from(self.0[k - 1] < *x);
+        }
+        // Undo the right turns taken after the last left turn: that left
+        // turn was at the lower bound of `x`.
+        k >>= k.trailing_ones() + 1;
+        k != 0 && self.0[k - 1] == *x
+    }
+}
+
+// -----------------------------
+// Sorting
+// -----------------------------
+
+#[derive(Clone, Copy)]
+enum Dist {
+    Sorted,
+    Reversed,
+    FewUnique,
+    Random,
+}
+
+impl Dist {
+    const ALL: [Dist; 4] = [Dist::Sorted, Dist::Reversed, Dist::FewUnique, Dist::Random];
+
+    fn name(self) -> &'static str {
+        match self {
+            Dist::Sorted => "sorted",
+            Dist::Reversed => "reversed",
+            Dist::FewUnique => "few_unique",
+            Dist::Random => "random",
+        }
+    }
+
+    fn keys(self, max: u64) -> Vec<u64> {
+        let mut data = Gen::new(&format!("sorting/{}", self.name()));
+        match self {
+            Dist::Sorted => data.sorted(N, 0..=max),
+            Dist::Reversed => data.reverse_sorted(N, 0..=max),
+            Dist::FewUnique => data.duplicates(N, FEW_UNIQUE),
+            Dist::Random => data.uniform(N, 0..=max),
+        }
+    }
+}
+
+fn sort_one<T: Elem>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    input: &[T],
+    sort: impl Fn(&mut Vec<T>),
+) {
+    group.bench_function(name, |b| {
+        b.iter_batched(
+            || input.to_vec(),
+            |mut v| {
+                sort(&mut v);
+                v
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn bench_sort<T: Elem>(c: &mut Criterion) {
+    for dist in Dist::ALL {
+        let input: Vec<T> = dist.keys(T::MAX).into_iter().map(T::from_key).collect();
+        let mut group = c.benchmark_group(format!("sort_{}_{}", T::NAME, dist.name()));
+        group.throughput(Throughput::Elements(N as u64));
+        sort_one(&mut group, "sort", &input, |v| v.sort());
+        sort_one(&mut group, "sort_unstable", &input, |v| v.sort_unstable());
+        sort_one(&mut group, "sort_by_key", &input, |v| v.sort_by_key(T::key));
+        sort_one(&mut group, "sort_by_cached_key", &input, |v| {
+            v.sort_by_cached_key(T::key)
+        });
+        // Only places the median; the rest is partitioned around it.
+        sort_one(&mut group, "select_nth_unstable", &input, |v| {
+            v.select_nth_unstable(N / 2);
+        });
+        if let Some(bytes) = T::RADIX_BYTES {
+            let (mut radix, mut expected) = (input.clone(), input.clone());
+            radix_sort(&mut radix, bytes);
+            expected.sort();
+            assert!(radix == expected, "radix sort of {} is wrong", T::NAME);
+            sort_one(&mut group, "radix", &input, |v| radix_sort(v, bytes));
+        }
+        group.finish();
+    }
+}
+
+fn bench_sorts(c: &mut Criterion) {
+    bench_sort::<u32>(c);
+    bench_sort::<u64>(c);
+    bench_sort::<String>(c);
+    bench_sort::<Record>(c);
+}
+
+// -----------------------------
+// Searching
+// -----------------------------
+//
+// Present keys are the even numbers 0, 2, .., 2(n-1); odd keys always miss.
+
+fn search_one<T: Elem>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    n: usize,
+    probes: &[T],
+    contains: impl Fn(&T) -> bool,
+) {
+    group.bench_function(BenchmarkId::new(name, n), |b| {
+        b.iter(|| {
+            let mut hits = 0usize;
+            for x in probes {
+                hits += contains(black_box(x)) as usize;
+            }
+            black_box(hits)
+        })
+    });
+}
+
+fn bench_search<T: Elem>(c: &mut Criterion) {
+    for outcome in ["hit", "miss"] {
+        let mut group = c.benchmark_group(format!("search_{outcome}_{}", T::NAME));
+        group.throughput(Throughput::Elements(PROBES as u64));
+        for n in SEARCH_SIZES {
+            let sorted: Vec<T> = (0..n as u64).map(|i| T::from_key(2 * i)).collect();
+            let tree = Eytzinger::new(&sorted);
+            let odd = u64::from(outcome == "miss");
+            let probes: Vec<T> = Gen::new(&format!("sorting/probes/{n}"))
+                .uniform(PROBES, 0..n as u64)
+                .into_iter()
+                .map(|i| T::from_key(2 * i + odd))
+                .collect();
+            assert!(
+                probes.iter().all(|x| tree.contains(x) == (odd == 0)),
+                "eytzinger search of {} is wrong",
+                T::NAME
+            );
+
+            search_one(&mut group, "binary_search", n, &probes, |x| {
+                sorted.binary_search(x).is_ok()
+            });
+            search_one(&mut group, "partition_point", n, &probes, |x| {
+                let i = sorted.partition_point(|e| e < x);
+                i < sorted.len() && sorted[i] == *x
+            });
+            search_one(&mut group, "linear", n, &probes, |x| sorted.contains(x));
+            search_one(&mut group, "eytzinger", n, &probes, |x| tree.contains(x));
+        }
+        group.finish();
+    }
+}
+
+fn bench_searches(c: &mut Criterion) {
+    bench_search::<u32>(c);
+    bench_search::<u64>(c);
+    bench_search::<String>(c);
+    bench_search::<Record>(c);
+}
+
+criterion_group!(benches, bench_sorts, bench_searches);
+rust_examples::bench_main!(benches);
@@ -0,0 +1,435 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group};
+use rust_examples::alloc;
+use rust_examples::data::{Gen, Lengths};
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::sync::Arc;
+
+// Unlike `string_str`, which builds one literal-based string, every group
+// here works on seeded inputs, and every variant frees what it builds:
+// in `count-allocs` builds `check_no_leaks` counts deallocations against
+// allocations before timing.
+
+// Records per rendered document.
+const RECORDS: [usize; 2] = [16, 1_024];
+// Lengths of the single strings converted and cloned.
+const LENGTHS: [usize; 2] = [8, 256];
+// Numbers formatted per iteration.
+const NUMBERS: usize = 1_024;
+// Piece size when growing a string to a known length.
+const PIECE: &str = "0123456";
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
+
+/// Runs one iteration, drops its result and asserts everything it
+/// allocated was freed. Does nothing without `count-allocs`, so timed runs
+/// use the system allocator.
+fn check_no_leaks<R>(label: &str, f: impl FnOnce() -> R) {
+    if !alloc::ENABLED {
+        return;
+    }
+    let stats = alloc::report(label, || drop(black_box(f())));
+    assert_eq!(
+        stats.allocations,
+        stats.deallocations,
+        "{label} leaked {} allocations",
+        stats.allocations - stats.deallocations
+    );
+}
+
+// -----------------------------
+// Rendering records
+// -----------------------------
+//
+// Every variant renders the same `key: value\n` lines. Those taking `out`
+// write into a buffer kept across iterations; the rest return a new string.
+
+type Record = (String, String);
+
+fn records(n: usize) -> Vec<Record> {
+    let mut data = Gen::new(&format!("string_building/records/{n}"));
+    let keys = data.strings(n, Lengths::Uniform { min: 3, max: 12 });
+    let values = data.strings(n, Lengths::Uniform { min: 1, max: 40 });
+    keys.into_iter().zip(values).collect()
+}
+
+// A temporary `String` per line, copied into the buffer.
+fn render_format(records: &[Record], out: &mut String) {
+    out.clear();
+    for (key, value) in records {
+        out.push_str(&format!("{key}: {value}\n"));
+    }
+}
+
+// The same formatting, straight into the buffer.
+fn render_write(records: &[Record], out: &mut String) {
+    out.clear();
+    for (key, value) in records {
+        let _ = writeln!(out, "{key}: {value}");
+    }
+}
+
+fn render_push_str(records: &[Record], out: &mut String) {
+    out.clear();
+    for (key, value) in records {
+        out.push_str(key);
+        out.push_str(": ");
+        out.push_str(value);
+        out.push('\n');
+    }
+}
+
+fn render_concat(records: &[Record], out: &mut String) {
+    let lines: Vec<String> = records
+        .iter()
+        .map(|(key, value)| [key.as_str(), ": ", value, "\n"].concat())
+        .collect();
+    *out = lines.concat();
+}
+
+fn render_join(records: &[Record], out: &mut String) {
+    let lines: Vec<String> = records
+        .iter()
+        .map(|(key, value)| [key.as_str(), value].join(": "))
+        .collect();
+    *out = lines.join("\n");
+    out.push('\n');
+}
+
+type Render = (&'static str, fn(&[Record], &mut String));
+
+const RENDERS: [Render; 5] = [
+    ("format", render_format),
+    ("write", render_write),
+    ("push_str", render_push_str),
+    ("concat", render_concat),
+    ("join", render_join),
+];
+
+fn bench_render(c: &mut Criterion) {
+    for n in RECORDS {
+        let records = records(n);
+        let group = format!("string_render_{n}");
+        let mut expected = String::new();
+        render_push_str(&records, &mut expected);
+
+        for (name, f) in RENDERS {
+            let mut out = String::new();
+            f(&records, &mut out);
+            assert_eq!(out, expected, "{group}/{name} rendered different text");
+            check_no_leaks(&format!("{group}/{name}"), || {
+                let mut out = String::new();
+                f(&records, &mut out);
+                out
+            });
+        }
+
+        let mut g = c.benchmark_group(group);
+        g.throughput(Throughput::Bytes(expected.len() as u64));
+        for (name, f) in RENDERS {
+            let mut out = String::new();
+            g.bench_function(name, |b| {
+                b.iter(|| {
+                    f(black_box(&records), &mut out);
+                    black_box(&out);
+                })
+            });
+        }
+        g.finish();
+    }
+}
+
+// -----------------------------
+// Capacity
+// -----------------------------
+
+fn grow(len: usize) -> String {
+    let mut s = String::new();
+    while s.len() < len {
+        s.push_str(PIECE);
+    }
+    s
+}
+
+fn with_capacity(len: usize) -> String {
+    let mut s = String::with_capacity(len.next_multiple_of(PIECE.len()));
+    while s.len() < len {
+        s.push_str(PIECE);
+    }
+    s
+}
+
+type Build = (&'static str, fn(usize) -> String);
+
+const BUILDS: [Build; 2] = [("grow", grow), ("with_capacity", with_capacity)];
+
+fn bench_capacity(c: &mut Criterion) {
+    for len in [64, 4_096, 262_144] {
+        let group = format!("string_capacity_{len}");
+        for (name, f) in BUILDS {
+            assert_eq!(f(len), grow(len), "{group}/{name} built a different string");
+            check_no_leaks(&format!("{group}/{name}"), || f(len));
+        }
+
+        let mut g = c.benchmark_group(group);
+        g.throughput(Throughput::Bytes(len as u64));
+        for (name, f) in BUILDS {
+            g.bench_function(name, |b| b.iter(|| f(black_box(len))));
+        }
+        g.finish();
+    }
+}
+
+// -----------------------------
+// Owned strings from `&str`
+// -----------------------------
+
+fn via_to_string(s: &str) -> String {
+    s.to_string()
+}
+
+fn via_to_owned(s: &str) -> String {
+    s.to_owned()
+}
+
+fn via_into(s: &str) -> String {
+    s.into()
+}
+
+fn via_string_from(s: &str) -> String {
+    String::from(s)
+}
+
+// The formatting machinery, for a string with nothing to format.
+#[allow(clippy::useless_format)]
+fn via_format(s: &str) -> String {
+    format!("{s}")
+}
+
+type Conversion = (&'static str, fn(&str) -> String);
+
+const CONVERSIONS: [Conversion; 5] = [
+    ("to_string", via_to_string),
+    ("to_owned", via_to_owned),
+    ("into", via_into),
+    ("string_from", via_string_from),
+    ("format", via_format),
+];
+
+fn source(len: usize) -> String {
+    let mut data = Gen::new(&format!("string_building/source/{len}"));
+    data.strings(1, Lengths::Fixed(len)).remove(0)
+}
+
+fn bench_owned(c: &mut Criterion) {
+    for len in LENGTHS {
+        let s = source(len);
+        let group = format!("string_owned_{len}");
+        for (name, f) in CONVERSIONS {
+            assert_eq!(f(&s), s, "{group}/{name} changed the string");
+            check_no_leaks(&format!("{group}/{name}"), || f(&s));
+        }
+
+        let mut g = c.benchmark_group(group);
+        for (name, f) in CONVERSIONS {
+            g.bench_function(name, |b| b.iter(|| f(black_box(&s))));
+        }
+        g.finish();
+    }
+}
+
+// -----------------------------
+// Shared string types
+// -----------------------------
+//
+// A string held for a long time and handed out many times: what does each
+// type cost to build once, and then per clone?
+
+fn bench_shared(c: &mut Criterion) {
+    for len in LENGTHS {
+        let s = source(len);
+        let string = s.clone();
+        let boxed: Box<str> = s.as_str().into();
+        let arc: Arc<str> = s.as_str().into();
+        let borrowed: Cow<'_, str> = Cow::Borrowed(&s);
+        let owned: Cow<'_, str> = Cow::Owned(s.clone());
+
+        let group = format!("string_shared_build_{len}");
+        check_no_leaks(&format!("{group}/string"), || s.to_owned());
+        check_no_leaks(&format!("{group}/box_str"), || Box::<str>::from(s.as_str()));
+        check_no_leaks(&format!("{group}/arc_str"), || Arc::<str>::from(s.as_str()));
+        check_no_leaks(&format!("{group}/cow_borrowed"), || {
+            Cow::Borrowed(s.as_str())
+        });
+        let mut g = c.benchmark_group(group);
+        g.bench_function("string", |b| b.iter(|| black_box(s.as_str()).to_owned()));
+        g.bench_function("box_str", |b| {
+            b.iter(|| Box::<str>::from(black_box(s.as_str())))
+        });
+        g.bench_function("arc_str", |b| {
+            b.iter(|| Arc::<str>::from(black_box(s.as_str())))
+        });
+        g.bench_function("cow_borrowed", |b| {
+            b.iter(|| Cow::Borrowed(black_box(s.as_str())))
+        });
+        g.finish();
+
+        let group = format!("string_shared_clone_{len}");
+        check_no_leaks(&format!("{group}/string"), || string.clone());
+        check_no_leaks(&format!("{group}/box_str"), || boxed.clone());
+        check_no_leaks(&format!("{group}/arc_str"), || arc.clone());
+        check_no_leaks(&format!("{group}/cow_borrowed"), || borrowed.clone());
+        check_no_leaks(&format!("{group}/cow_owned"), || owned.clone());
+        let mut g = c.benchmark_group(group);
+        g.bench_function("string", |b| b.iter(|| black_box(&string).clone()));
+        g.bench_function("box_str", |b| b.iter(|| black_box(&boxed).clone()));
+        g.bench_function("arc_str", |b| b.iter(|| black_box(&arc).clone()));
+        g.bench_function("cow_borrowed", |b| b.iter(|| black_box(&borrowed).clone()));
+        g.bench_function("cow_owned", |b| b.iter(|| black_box(&owned).clone()));
+        g.finish();
+    }
+}
+
+// -----------------------------
+// Numbers
+// -----------------------------
+//
+// Each variant formats every number and returns the total length, so the
+// per-number strings of `to_string` and `format` are freed as they go.
+
+fn u64_to_string(v: &[u64], _: &mut String) -> usize {
+    v.iter().map(|x| x.to_string().len()).sum()
+}
+
+fn u64_format(v: &[u64], _: &mut String) -> usize {
+    v.iter().map(|x| format!("{x}").len()).sum()
+}
+
+fn u64_write(v: &[u64], buf: &mut String) -> usize {
+    let mut total = 0;
+    for x in v {
+        buf.clear();
+        let _ = write!(buf, "{x}");
+        total += buf.len();
+    }
+    total
+}
+
+// Digits into a stack buffer, back to front: no formatting machinery.
+fn u64_manual(v: &[u64], buf: &mut String) -> usize {
+    let mut total = 0;
+    for &x in v {
+        let mut digits = [0u8; 20];
+        let mut i = digits.len();
+        let mut x = x;
+        loop {
+            i -= 1;
+            digits[i] = b'0' + (x % 10) as u8;
+            x /= 10;
+            if x == 0 {
+                break;
+            }
+        }
+        buf.clear();
+        // Only ASCII digits were written.
+        buf.push_str(std::str::from_utf8(&digits[i..]).unwrap());
+        total += buf.len();
+    }
+    total
+}
+
+fn f64_to_string(v: &[f64], _: &mut String) -> usize {
+    v.iter().map(|x| x.to_string().len()).sum()
+}
+
+fn f64_format(v: &[f64], _: &mut String) -> usize {
+    v.iter().map(|x| format!("{x}").len()).sum()
+}
+
+fn f64_write(v: &[f64], buf: &mut String) -> usize {
+    let mut total = 0;
+    for x in v {
+        buf.clear();
+        let _ = write!(buf, "{x}");
+        total += buf.len();
+    }
+    total
+}
+
+// Fixed precision takes a different path through `core::fmt` than the
+// shortest round-tripping form above.
+fn f64_precision(v: &[f64], buf: &mut String) -> usize {
+    let mut total = 0;
+    for x in v {
+        buf.clear();
+        let _ = write!(buf, "{x:.3}");
+        total += buf.len();
+    }
+    total
+}
+
+type Formatter<T> = (&'static str, fn(&[T], &mut String) -> usize);
+
+const U64_FORMATTERS: [Formatter<u64>; 4] = [
+    ("to_string", u64_to_string),
+    ("format", u64_format),
+    ("write", u64_write),
+    ("manual", u64_manual),
+];
+
+const F64_FORMATTERS: [Formatter<f64>; 4] = [
+    ("to_string", f64_to_string),
+    ("format", f64_format),
+    ("write", f64_write),
+    ("precision", f64_precision),
+];
+
+fn bench_formatters<T>(c: &mut Criterion, group: &str, v: &[T], formatters: &[Formatter<T>]) {
+    for &(name, f) in formatters {
+        check_no_leaks(&format!("{group}/{name}"), || f(v, &mut String::new()));
+    }
+
+    let mut g = c.benchmark_group(group);
+    g.throughput(Throughput::Elements(v.len() as u64));
+    let mut buf = String::new();
+    for &(name, f) in formatters {
+        g.bench_function(name, |b| b.iter(|| f(black_box(v), &mut buf)));
+    }
+    g.finish();
+}
+
+fn bench_numbers(c: &mut Criterion) {
+    let mut data = Gen::new("string_building/numbers");
+    // Spread over every digit count, not mostly 19- and 20-digit values.
+    let ints: Vec<u64> = data
+        .uniform(NUMBERS, 0..64u32)
+        .into_iter()
+        .zip(data.uniform(NUMBERS, 0..u64::MAX))
+        .map(|(bits, x)| x >> bits)
+        .collect();
+    let floats = data.normal(NUMBERS, 0.0, 1_000.0);
+
+    let mut buf = String::new();
+    for &x in &ints {
+        u64_manual(&[x], &mut buf);
+        assert_eq!(
+            buf,
+            x.to_string(),
+            "string_number_u64/manual misformats {x}"
+        );
+    }
+    bench_formatters(c, "string_number_u64", &ints, &U64_FORMATTERS);
+    bench_formatters(c, "string_number_f64", &floats, &F64_FORMATTERS);
+}
+
+criterion_group!(
+    benches,
+    bench_render,
+    bench_capacity,
+    bench_owned,
+    bench_shared,
+    bench_numbers
+);
+rust_examples::bench_main!(benches);
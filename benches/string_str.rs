@@ -1,13 +1,17 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, Criterion};
+use std::borrow::Cow;
 
 #[derive(Clone)]
 struct StringWrapper {
     value: String,
 }
 
+// Borrows the literal until it is modified, then owns the new string, which
+// is freed with the wrapper instead of leaked.
+#[allow(dead_code)]
 #[derive(Clone)]
 struct StrWrapper<'a> {
-    value: &'a str,
+    value: Cow<'a, str>,
 }
 
 fn no_modification_string() -> StringWrapper {
@@ -18,7 +22,9 @@ fn no_modification_string() -> StringWrapper {
 
 fn no_modification_str<'a>() -> StrWrapper<'a> {
     let s: &'a str = "Hello, world!";
-    StrWrapper { value: s }
+    StrWrapper {
+        value: Cow::Borrowed(s),
+    }
 }
 
 fn modify_once_string() -> StringWrapper {
@@ -32,8 +38,9 @@ fn modify_once_string() -> StringWrapper {
 fn modify_once_str<'a>() -> StrWrapper<'a> {
     let s: &'a str = "Hello";
     let new_str = format!("{}, world!", s); // Create a new string, because `str` is immutable
-    let new_str_ref: &'a str = Box::leak(new_str.into_boxed_str());
-    StrWrapper { value: new_str_ref }
+    StrWrapper {
+        value: Cow::Owned(new_str),
+    }
 }
 
 fn modify_four_times_string() -> StringWrapper {
@@ -52,8 +59,9 @@ fn modify_four_times_str<'a>() -> StrWrapper<'a> {
     for _ in 0..4 {
         new_str.push_str(" world");
     }
-    let new_str_ref: &'a str = Box::leak(new_str.into_boxed_str());
-    StrWrapper { value: new_str_ref }
+    StrWrapper {
+        value: Cow::Owned(new_str),
+    }
 }
 
 fn modify_ten_times_string() -> StringWrapper {
@@ -72,8 +80,9 @@ fn modify_ten_times_str<'a>() -> StrWrapper<'a> {
     for _ in 0..10 {
         new_str.push_str(" world");
     }
-    let new_str_ref: &'a str = Box::leak(new_str.into_boxed_str());
-    StrWrapper { value: new_str_ref }
+    StrWrapper {
+        value: Cow::Owned(new_str),
+    }
 }
 
 fn modify_multiple_times_string() -> StringWrapper {
@@ -92,8 +101,9 @@ fn modify_multiple_times_str<'a>() -> StrWrapper<'a> {
     for _ in 0..1000 {
         new_str.push_str(" world");
     }
-    let new_str_ref: &'a str = Box::leak(new_str.into_boxed_str());
-    StrWrapper { value: new_str_ref }
+    StrWrapper {
+        value: Cow::Owned(new_str),
+    }
 }
 
 fn benchmark_string(c: &mut Criterion) {
@@ -151,4 +161,4 @@ fn benchmark_str(c: &mut Criterion) {
 }
 
 criterion_group!(benches, benchmark_string, benchmark_str);
-criterion_main!(benches);
+rust_examples::bench_main!(benches);
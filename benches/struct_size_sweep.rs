@@ -0,0 +1,86 @@
+use criterion::measurement::WallTime;
+use criterion::{BatchSize, BenchmarkGroup, BenchmarkId, Criterion, black_box, criterion_group};
+use rust_examples::fixtures::{BENCH_SIZE, Fixture};
+use rust_examples::sweep::{self, Layout, Visitor};
+
+// ---------------------------------------
+// Ways to hand a payload across a call boundary
+// ---------------------------------------
+
+#[inline(never)]
+fn pass_move<T>(value: T) -> T {
+    black_box(value)
+}
+
+#[inline(never)]
+fn pass_clone<T: Clone>(value: &T) -> T {
+    black_box(value.clone())
+}
+
+#[inline(never)]
+fn pass_ref<T>(value: &T) -> &T {
+    black_box(value)
+}
+
+#[inline(never)]
+fn pass_box<T>(value: Box<T>) -> Box<T> {
+    black_box(value)
+}
+
+// ---------------------------------------
+// Benches
+// ---------------------------------------
+
+// One group per layout; the parameter is the field count, so Criterion's
+// line chart shows where each variant starts to grow with struct size.
+struct Sweep<'a> {
+    group: BenchmarkGroup<'a, WallTime>,
+    layout: Layout,
+}
+
+impl Visitor for Sweep<'_> {
+    fn visit<T: Fixture + 'static>(&mut self, layout: Layout) {
+        if layout != self.layout {
+            return;
+        }
+        let g = &mut self.group;
+
+        // Building and dropping happen outside the timed part for move/box.
+        g.bench_function(BenchmarkId::new("move", T::FIELDS), |b| {
+            b.iter_batched(|| T::build(BENCH_SIZE), pass_move, BatchSize::SmallInput)
+        });
+
+        g.bench_function(BenchmarkId::new("box", T::FIELDS), |b| {
+            b.iter_batched(
+                || Box::new(T::build(BENCH_SIZE)),
+                pass_box,
+                BatchSize::SmallInput,
+            )
+        });
+
+        let value = T::build(BENCH_SIZE);
+        g.bench_function(BenchmarkId::new("clone", T::FIELDS), |b| {
+            b.iter(|| pass_clone(&value))
+        });
+
+        g.bench_function(BenchmarkId::new("ref", T::FIELDS), |b| {
+            b.iter(|| {
+                black_box(pass_ref(&value));
+            })
+        });
+    }
+}
+
+fn bench_sweep(c: &mut Criterion) {
+    for layout in [Layout::Pod, Layout::Mixed] {
+        let mut sweep = Sweep {
+            group: c.benchmark_group(format!("struct_sweep_{}", layout.name())),
+            layout,
+        };
+        sweep::visit_all(&mut sweep);
+        sweep.group.finish();
+    }
+}
+
+criterion_group!(benches, bench_sweep);
+rust_examples::bench_main!(benches);
@@ -1,6 +1,17 @@
-use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use criterion::measurement::Measurement;
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group};
+use rust_examples::alloc;
+use rust_examples::data::Gen;
+use rust_examples::perf;
 use std::error::Error;
 use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
 
 // ==== Original dispatch benchmarks ====
 
@@ -29,11 +40,11 @@ fn use_case_generic<R: UserRepo>(repo: &R, id: u64) -> u64 {
     repo.get_user(id)
 }
 
-fn bench_dispatch_overhead(c: &mut Criterion) {
+fn bench_dispatch_overhead<M: Measurement + 'static>(c: &mut Criterion<M>, prefix: &str) {
     let repo = ConcreteRepo;
     let repo_trait: &dyn UserRepo = &repo;
 
-    c.bench_function("original_concrete_call", |b| {
+    c.bench_function(&format!("{prefix}original_concrete_call"), |b| {
         b.iter(|| {
             let mut sum = 0;
             for i in 0..1_000_000 {
@@ -43,7 +54,7 @@ fn bench_dispatch_overhead(c: &mut Criterion) {
         })
     });
 
-    c.bench_function("original_trait_object_call", |b| {
+    c.bench_function(&format!("{prefix}original_trait_object_call"), |b| {
         b.iter(|| {
             let mut sum = 0;
             for i in 0..1_000_000 {
@@ -54,7 +65,7 @@ fn bench_dispatch_overhead(c: &mut Criterion) {
     });
 
     // NEW: generic static-dispatch benchmark
-    c.bench_function("generic_monomorphized_call", |b| {
+    c.bench_function(&format!("{prefix}generic_monomorphized_call"), |b| {
         b.iter(|| {
             let mut sum = 0;
             for i in 0..1_000_000 {
@@ -65,28 +76,225 @@ fn bench_dispatch_overhead(c: &mut Criterion) {
     });
 }
 
+// ==== Polymorphism degree ====
+
+// Call sites per measured iteration, spread evenly over the implementors.
+const SITES: usize = 4096;
+
+// Each body is built from different operations. Bodies that differed only
+// in a constant would let LLVM turn the enum `match` into a lookup table of
+// constants with no branch at all (`run-suite asm trait_direct --check`).
+macro_rules! repos {
+    ($($name:ident => |$id:ident| $body:expr),* $(,)?) => {
+        $(
+            struct $name;
+
+            impl UserRepo for $name {
+                fn get_user(&self, $id: u64) -> u64 {
+                    $body
+                }
+            }
+        )*
+    };
+}
+
+repos!(
+    Repo0 => |id| id.wrapping_mul(3) ^ 3,
+    Repo1 => |id| id.rotate_left(5).wrapping_add(1),
+    Repo2 => |id| (id >> 7) | (id << 3),
+    Repo3 => |id| id.wrapping_add(u64::from(id.count_ones())),
+    Repo4 => |id| id.wrapping_sub(13).swap_bytes(),
+    Repo5 => |id| id / 17,
+    Repo6 => |id| id.wrapping_mul(19) ^ u64::from(id.leading_zeros()),
+    Repo7 => |id| id.wrapping_add(23).reverse_bits(),
+);
+
+// Closed set: the compiler sees every implementor and can inline each arm.
+#[derive(Clone, Copy)]
+enum RepoImpl {
+    R0,
+    R1,
+    R2,
+    R3,
+    R4,
+    R5,
+    R6,
+    R7,
+}
+
+impl RepoImpl {
+    const ALL: [RepoImpl; 8] = [
+        RepoImpl::R0,
+        RepoImpl::R1,
+        RepoImpl::R2,
+        RepoImpl::R3,
+        RepoImpl::R4,
+        RepoImpl::R5,
+        RepoImpl::R6,
+        RepoImpl::R7,
+    ];
+
+    fn get_user(self, id: u64) -> u64 {
+        match self {
+            RepoImpl::R0 => Repo0.get_user(id),
+            RepoImpl::R1 => Repo1.get_user(id),
+            RepoImpl::R2 => Repo2.get_user(id),
+            RepoImpl::R3 => Repo3.get_user(id),
+            RepoImpl::R4 => Repo4.get_user(id),
+            RepoImpl::R5 => Repo5.get_user(id),
+            RepoImpl::R6 => Repo6.get_user(id),
+            RepoImpl::R7 => Repo7.get_user(id),
+        }
+    }
+
+    fn boxed(self) -> Box<dyn UserRepo> {
+        match self {
+            RepoImpl::R0 => Box::new(Repo0),
+            RepoImpl::R1 => Box::new(Repo1),
+            RepoImpl::R2 => Box::new(Repo2),
+            RepoImpl::R3 => Box::new(Repo3),
+            RepoImpl::R4 => Box::new(Repo4),
+            RepoImpl::R5 => Box::new(Repo5),
+            RepoImpl::R6 => Box::new(Repo6),
+            RepoImpl::R7 => Box::new(Repo7),
+        }
+    }
+}
+
+const FN_TABLE: [fn(u64) -> u64; 8] = [
+    |id| Repo0.get_user(id),
+    |id| Repo1.get_user(id),
+    |id| Repo2.get_user(id),
+    |id| Repo3.get_user(id),
+    |id| Repo4.get_user(id),
+    |id| Repo5.get_user(id),
+    |id| Repo6.get_user(id),
+    |id| Repo7.get_user(id),
+];
+
+// Every strategy sums its call sites in an out-of-line loop of its own, so
+// each pays the same one call per iteration, and `run-suite asm` can find
+// the inlined `match` in `sum_enum` by name.
+#[inline(never)]
+fn sum_enum(repos: &[RepoImpl]) -> u64 {
+    let mut sum = 0u64;
+    for (id, repo) in repos.iter().enumerate() {
+        sum = sum.wrapping_add(repo.get_user(id as u64));
+    }
+    sum
+}
+
+#[inline(never)]
+fn sum_fn(fns: &[fn(u64) -> u64]) -> u64 {
+    let mut sum = 0u64;
+    for (id, f) in fns.iter().enumerate() {
+        sum = sum.wrapping_add(f(id as u64));
+    }
+    sum
+}
+
+/// One instance each for `Box`, `Rc` and `Arc`.
+#[inline(never)]
+fn sum_dyn<P: Deref<Target = dyn UserRepo>>(repos: &[P]) -> u64 {
+    let mut sum = 0u64;
+    for (id, repo) in repos.iter().enumerate() {
+        sum = sum.wrapping_add(repo.get_user(id as u64));
+    }
+    sum
+}
+
+/// Which implementor serves each call site: `degree` kinds in equal shares.
+/// "interleaved" is a seeded shuffle (a round-robin pattern would be learnt
+/// by the branch predictor); "sorted" runs each kind back to back.
+fn call_sites(degree: usize, order: &str) -> Vec<usize> {
+    let mut kinds: Vec<usize> = (0..SITES).map(|i| i % degree).collect();
+    match order {
+        "interleaved" => Gen::new("trait_direct/call_sites").shuffle(&mut kinds),
+        _ => kinds.sort_unstable(),
+    }
+    kinds
+}
+
+fn bench_polymorphism<M: Measurement + 'static>(c: &mut Criterion<M>, prefix: &str) {
+    for order in ["interleaved", "sorted"] {
+        let mut group = c.benchmark_group(format!("{prefix}dispatch_{order}"));
+        for degree in [1, 2, 4, 8] {
+            let kinds = call_sites(degree, order);
+            // black_box keeps the compiler from proving a single
+            // implementor when degree is 1.
+            let enums: Vec<RepoImpl> = black_box(kinds.iter().map(|&k| RepoImpl::ALL[k]).collect());
+            let fns: Vec<fn(u64) -> u64> = black_box(kinds.iter().map(|&k| FN_TABLE[k]).collect());
+            let boxes: Vec<Box<dyn UserRepo>> =
+                black_box(kinds.iter().map(|&k| RepoImpl::ALL[k].boxed()).collect());
+            let rcs: Vec<Rc<dyn UserRepo>> = black_box(
+                kinds
+                    .iter()
+                    .map(|&k| Rc::from(RepoImpl::ALL[k].boxed()))
+                    .collect(),
+            );
+            let arcs: Vec<Arc<dyn UserRepo>> = black_box(
+                kinds
+                    .iter()
+                    .map(|&k| Arc::from(RepoImpl::ALL[k].boxed()))
+                    .collect(),
+            );
+
+            group.bench_function(BenchmarkId::new("enum_match", degree), |b| {
+                b.iter(|| black_box(sum_enum(&enums)))
+            });
+
+            group.bench_function(BenchmarkId::new("fn_pointer", degree), |b| {
+                b.iter(|| black_box(sum_fn(&fns)))
+            });
+
+            group.bench_function(BenchmarkId::new("box_dyn", degree), |b| {
+                b.iter(|| black_box(sum_dyn(&boxes)))
+            });
+
+            group.bench_function(BenchmarkId::new("rc_dyn", degree), |b| {
+                b.iter(|| black_box(sum_dyn(&rcs)))
+            });
+
+            group.bench_function(BenchmarkId::new("arc_dyn", degree), |b| {
+                b.iter(|| black_box(sum_dyn(&arcs)))
+            });
+        }
+        group.finish();
+    }
+}
+
 // ==== Error benchmarks ====
 
+// Carries a code so boxing it allocates: a zero-sized error would make
+// `Box::new` free and hide the cost `error_rate` measures.
 #[derive(Debug)]
-struct MyError;
+struct MyError {
+    code: u32,
+}
 
 impl fmt::Display for MyError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "MyError")
+        write!(f, "MyError({})", self.code)
     }
 }
 
 impl Error for MyError {}
 
+const ERROR_CODE: u32 = 404;
+
 fn concrete_error_fn(success: bool) -> Result<u64, MyError> {
-    if success { Ok(42) } else { Err(MyError) }
+    if success {
+        Ok(42)
+    } else {
+        Err(MyError { code: ERROR_CODE })
+    }
 }
 
 fn dynamic_error_fn(success: bool) -> Result<u64, Box<dyn Error>> {
     if success {
         Ok(42)
     } else {
-        Err(Box::new(MyError))
+        Err(Box::new(MyError { code: ERROR_CODE }))
     }
 }
 
@@ -120,9 +328,8 @@ fn bench_error_dispatch(c: &mut Criterion) {
         b.iter(|| {
             let mut sum = 0;
             for _ in 0..n {
-                match black_box(concrete_error_fn(true)) {
-                    Ok(v) => sum += v,
-                    Err(_) => {}
+                if let Ok(v) = black_box(concrete_error_fn(true)) {
+                    sum += v;
                 }
             }
             black_box(sum)
@@ -133,9 +340,8 @@ fn bench_error_dispatch(c: &mut Criterion) {
         b.iter(|| {
             let mut sum = 0;
             for _ in 0..n {
-                match black_box(dynamic_error_fn(true)) {
-                    Ok(v) => sum += v,
-                    Err(_) => {}
+                if let Ok(v) = black_box(dynamic_error_fn(true)) {
+                    sum += v;
                 }
             }
             black_box(sum)
@@ -146,9 +352,8 @@ fn bench_error_dispatch(c: &mut Criterion) {
         b.iter(|| {
             let mut sum = 0;
             for _ in 0..n {
-                match black_box(enum_error_fn(true)) {
-                    Ok(v) => sum += v,
-                    Err(_) => {}
+                if let Ok(v) = black_box(enum_error_fn(true)) {
+                    sum += v;
                 }
             }
             black_box(sum)
@@ -156,7 +361,109 @@ fn bench_error_dispatch(c: &mut Criterion) {
     });
 }
 
+// ==== Error rate sweep ====
+
+// Calls per measured iteration; exactly `pct`% of them fail.
+const ERROR_SITES: usize = 10_000;
+
+/// Seeded shuffle, so failures are spread unpredictably rather than in a
+/// block the branch predictor could learn.
+fn outcomes(pct: usize) -> Vec<bool> {
+    let failures = ERROR_SITES * pct / 100;
+    let mut out: Vec<bool> = (0..ERROR_SITES).map(|i| i >= failures).collect();
+    Gen::new("trait_direct/outcomes").shuffle(&mut out);
+    out
+}
+
+/// Asserts that every failed `dynamic` call allocates its box exactly once,
+/// in `count-allocs` builds only.
+fn check_dynamic_allocs() {
+    if !alloc::ENABLED {
+        return;
+    }
+    let outcomes = outcomes(100);
+    let stats = alloc::report("error_rate/dynamic/100", || {
+        for &ok in &outcomes {
+            drop(black_box(dynamic_error_fn(black_box(ok))));
+        }
+    });
+    assert_eq!(
+        stats.allocations, ERROR_SITES as u64,
+        "error_rate/dynamic should allocate once per Err"
+    );
+}
+
+// The `true`-only loops above never build an error; these do, so the
+// `Box<dyn Error>` allocation shows up in proportion to the error rate.
+fn bench_error_rate(c: &mut Criterion) {
+    check_dynamic_allocs();
+    let mut group = c.benchmark_group("error_rate");
+    for pct in [0, 1, 10, 50, 100] {
+        let outcomes = outcomes(pct);
+
+        group.bench_function(BenchmarkId::new("concrete", pct), |b| {
+            b.iter(|| {
+                let (mut sum, mut errors) = (0u64, 0u64);
+                for &ok in &outcomes {
+                    match black_box(concrete_error_fn(black_box(ok))) {
+                        Ok(v) => sum += v,
+                        Err(_) => errors += 1,
+                    }
+                }
+                black_box((sum, errors))
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("dynamic", pct), |b| {
+            b.iter(|| {
+                let (mut sum, mut errors) = (0u64, 0u64);
+                for &ok in &outcomes {
+                    match black_box(dynamic_error_fn(black_box(ok))) {
+                        Ok(v) => sum += v,
+                        Err(_) => errors += 1,
+                    }
+                }
+                black_box((sum, errors))
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("enum", pct), |b| {
+            b.iter(|| {
+                let (mut sum, mut errors) = (0u64, 0u64);
+                for &ok in &outcomes {
+                    match black_box(enum_error_fn(black_box(ok))) {
+                        Ok(v) => sum += v,
+                        Err(_) => errors += 1,
+                    }
+                }
+                black_box((sum, errors))
+            })
+        });
+    }
+    group.finish();
+}
+
 // ==== Benchmark groups ====
 
-criterion_group!(benches, bench_dispatch_overhead, bench_error_dispatch);
-criterion_main!(benches);
+fn bench_dispatch_wall_time(c: &mut Criterion) {
+    bench_dispatch_overhead(c, "");
+    bench_polymorphism(c, "");
+}
+
+// Dispatch differences are a handful of instructions per call, so repeat
+// them under a CPU counter (see `rust_examples::perf`).
+fn counters() {
+    perf::run_counted(|c, label| {
+        let prefix = format!("{label}/");
+        bench_dispatch_overhead(c, &prefix);
+        bench_polymorphism(c, &prefix);
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_dispatch_wall_time,
+    bench_error_dispatch,
+    bench_error_rate
+);
+rust_examples::bench_main!(benches, counters);
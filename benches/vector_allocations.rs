@@ -1,4 +1,9 @@
-use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use criterion::{Criterion, Throughput, black_box, criterion_group};
+use rust_examples::alloc;
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
 
 fn make_and_fill_prealloc<const N: usize>() {
     // Allocate with exact capacity so pushes won't reallocate.
@@ -23,15 +28,29 @@ fn make_and_fill_growing<const N: usize>() {
 }
 
 fn bench_make_and_fill_for<const N: usize>(c: &mut Criterion) {
-    let mut g = c.benchmark_group(format!("vec_make_and_fill_N={}", N));
+    let group = format!("vec_make_and_fill_N={}", N);
+
+    if alloc::ENABLED {
+        // Exact capacity: one allocation, never a realloc.
+        let prealloc = alloc::report(
+            &format!("{group}/with_capacity"),
+            make_and_fill_prealloc::<N>,
+        );
+        assert_eq!((prealloc.allocations, prealloc.reallocations), (1, 0));
+        // Growing: reallocates on the way up once N outgrows the first capacity.
+        let growing = alloc::report(&format!("{group}/new"), make_and_fill_growing::<N>);
+        assert!(N <= 4 || growing.reallocations > 0);
+    }
+
+    let mut g = c.benchmark_group(group);
     g.throughput(Throughput::Elements(N as u64));
 
     g.bench_function("Vec::with_capacity(N) then push N", |b| {
-        b.iter(|| make_and_fill_prealloc::<N>())
+        b.iter(make_and_fill_prealloc::<N>)
     });
 
     g.bench_function("Vec::new() then push N (growing)", |b| {
-        b.iter(|| make_and_fill_growing::<N>())
+        b.iter(make_and_fill_growing::<N>)
     });
 
     g.finish();
@@ -46,4 +65,4 @@ fn benches(c: &mut Criterion) {
 }
 
 criterion_group!(benches_group, benches);
-criterion_main!(benches_group);
+rust_examples::bench_main!(benches_group);
@@ -1,21 +1,51 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use rand::prelude::*;
+use criterion::{black_box, criterion_group, Criterion};
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, BenchmarkId, Throughput};
+use rust_examples::data::Gen;
+use rust_examples::hashers::{self, Key};
 use std::collections::{HashMap, BTreeMap};
+use std::hash::BuildHasher;
+
+// HashMap lookup with each hasher (see `rust_examples::hashers`), so the
+// map is not only measured with SipHash.
+struct MapSearch<'c, 'v> {
+    c: &'c mut Criterion,
+    values: &'v [u32],
+    search_value: u32,
+    size: usize,
+}
+
+impl hashers::Visitor for MapSearch<'_, '_> {
+    fn visit<K: Key, S: BuildHasher + Default + Clone>(&mut self, hasher: &'static str) {
+        let mut map: HashMap<K, (), S> =
+            HashMap::with_capacity_and_hasher(self.size, S::default());
+        map.extend(self.values.iter().map(|&v| (K::nth(v.into()), ())));
+        let search_value = K::nth(self.search_value.into());
+
+        // SipHash keeps the id it had before the hasher matrix.
+        let map_bench_id = if hasher == hashers::DEFAULT {
+            format!("map_search_{} elements", self.size)
+        } else {
+            format!("map_search_{}_{} elements", hasher, self.size)
+        };
+        self.c.bench_function(&map_bench_id, |b| {
+            b.iter(|| {
+                black_box(map.get(&search_value));
+            })
+        });
+    }
+}
 
 fn bench_vec_and_map_search(c: &mut Criterion) {
     // Sizes to test
     let sizes = [10, 100, 1_000, 10_000, 100_000, 1_000_000, 10_000_000];
 
     for &size in &sizes {
-        // Create a new unsorted Vec and HashMap for this size
-        let mut rng = rand::rng();
-
-        let vec: Vec<u32> = (0..size).map(|_| rng.random_range(0..size as u32)).collect();
-        let mut map: HashMap<u32, ()> = HashMap::with_capacity(size);
+        // Create a new unsorted Vec and BTreeMap for this size
+        let vec: Vec<u32> = Gen::new(&format!("vector_map/{size}")).uniform(size, 0..size as u32);
         let mut btree_map: BTreeMap<u32, ()> = BTreeMap::new();
 
         for &v in &vec {
-            map.insert(v, ());
             btree_map.insert(v, ());
         }
 
@@ -34,13 +64,9 @@ fn bench_vec_and_map_search(c: &mut Criterion) {
         //     })
         // });
 
-        // Benchmark: HashMap lookup
-        let map_bench_id = format!("map_search_{} elements", size);
-        c.bench_function(&map_bench_id, |b| {
-            b.iter(|| {
-                black_box(map.get(&search_value));
-            })
-        });
+        // Benchmark: HashMap lookup, once per hasher
+        let mut map_search = MapSearch { c, values: &vec, search_value, size };
+        hashers::visit_key(u32::NAME, &mut map_search);
 
         // Benchmark: BTreeMap lookup
         let btree_map_bench_id = format!("btree_map_search_{} elements", size);
@@ -70,5 +96,45 @@ fn bench_vec_and_map_search(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, bench_vec_and_map_search);
-criterion_main!(benches);
+// Hasher matrix (see `rust_examples::hashers`): a batch of lookups, about
+// half of them misses, against maps of each key type and hasher.
+const PROBES: usize = 1_000;
+
+struct LookupMatrix<'g, 'a> {
+    group: &'g mut BenchmarkGroup<'a, WallTime>,
+}
+
+impl hashers::Visitor for LookupMatrix<'_, '_> {
+    fn visit<K: Key, S: BuildHasher + Default + Clone>(&mut self, hasher: &'static str) {
+        for n in [1_000u64, 100_000] {
+            let mut map: HashMap<K, (), S> =
+                HashMap::with_capacity_and_hasher(n as usize, S::default());
+            map.extend((0..n).map(|i| (K::nth(i), ())));
+
+            let mut data = Gen::new(&format!("vector_map/probes/{n}"));
+            let probes: Vec<K> = (0..PROBES).map(|_| K::nth(data.below(0..2 * n))).collect();
+
+            self.group.bench_function(BenchmarkId::new(hasher, n), |b| {
+                b.iter(|| {
+                    let mut hits = 0usize;
+                    for k in &probes {
+                        hits += map.contains_key(k) as usize;
+                    }
+                    black_box(hits)
+                })
+            });
+        }
+    }
+}
+
+fn bench_hasher_matrix(c: &mut Criterion) {
+    for &key in hashers::KEYS {
+        let mut group = c.benchmark_group(format!("map_hasher_lookup_{key}"));
+        group.throughput(Throughput::Elements(PROBES as u64));
+        hashers::visit_key(key, &mut LookupMatrix { group: &mut group });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_vec_and_map_search, bench_hasher_matrix);
+rust_examples::bench_main!(benches);
@@ -1,4 +1,4 @@
-use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use criterion::{Criterion, Throughput, black_box, criterion_group};
 use smallvec::SmallVec;
 use tinyvec::TinyVec;
 
@@ -78,26 +78,26 @@ fn bench_make_and_fill_all_for<const N: usize>(c: &mut Criterion) {
 
     // Vec
     g.bench_function("Vec::with_capacity(N) + push N", |b| {
-        b.iter(|| make_and_fill_vec_prealloc::<N>())
+        b.iter(make_and_fill_vec_prealloc::<N>)
     });
     g.bench_function("Vec::new() + push N (growing)", |b| {
-        b.iter(|| make_and_fill_vec_grow::<N>())
+        b.iter(make_and_fill_vec_grow::<N>)
     });
 
     // SmallVec
     g.bench_function("SmallVec::with_capacity(N) + push N", |b| {
-        b.iter(|| make_and_fill_smallvec_prealloc::<N>())
+        b.iter(make_and_fill_smallvec_prealloc::<N>)
     });
     g.bench_function("SmallVec::new() + push N (growing/inline→spill)", |b| {
-        b.iter(|| make_and_fill_smallvec_grow::<N>())
+        b.iter(make_and_fill_smallvec_grow::<N>)
     });
 
     // TinyVec
     g.bench_function("TinyVec::with_capacity(N) + push N", |b| {
-        b.iter(|| make_and_fill_tinyvec_prealloc::<N>())
+        b.iter(make_and_fill_tinyvec_prealloc::<N>)
     });
     g.bench_function("TinyVec::new() + push N (growing/inline→spill)", |b| {
-        b.iter(|| make_and_fill_tinyvec_grow::<N>())
+        b.iter(make_and_fill_tinyvec_grow::<N>)
     });
 
     g.finish();
@@ -112,4 +112,4 @@ fn benches(c: &mut Criterion) {
 }
 
 criterion_group!(benches_group, benches);
-criterion_main!(benches_group);
+rust_examples::bench_main!(benches_group);
@@ -1,4 +1,5 @@
-use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use criterion::{Criterion, Throughput, black_box, criterion_group};
+use rust_examples::alloc;
 use smallvec::SmallVec;
 use tinyvec::TinyVec;
 
@@ -6,6 +7,10 @@ use tinyvec::TinyVec;
 type SmallInline = [u32; 128];
 const TINY_INLINE: usize = 100;
 
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
+
 // -----------------------------
 // Make & fill (alloc + push are timed)
 // -----------------------------
@@ -44,17 +49,35 @@ fn make_and_fill_tinyvec_prealloc<const N: usize>() {
 // -----------------------------
 
 fn bench_make_and_fill_for<const N: usize>(c: &mut Criterion) {
-    let mut g = c.benchmark_group(format!("make_and_fill_prealloc_N={}", N));
+    let group = format!("make_and_fill_prealloc_N={}", N);
+
+    if alloc::ENABLED {
+        // Inline types must not touch the heap while N fits their inline capacity.
+        let vec = alloc::report(&format!("{group}/Vec"), make_and_fill_vec_prealloc::<N>);
+        assert_eq!(vec.allocations, 1);
+        let small = alloc::report(
+            &format!("{group}/SmallVec"),
+            make_and_fill_smallvec_prealloc::<N>,
+        );
+        assert_eq!(small.allocations, u64::from(N > 128));
+        let tiny = alloc::report(
+            &format!("{group}/TinyVec"),
+            make_and_fill_tinyvec_prealloc::<N>,
+        );
+        assert_eq!(tiny.allocations, u64::from(N > TINY_INLINE));
+    }
+
+    let mut g = c.benchmark_group(group);
     g.throughput(Throughput::Elements(N as u64));
 
     g.bench_function("Vec::with_capacity(N) + push N", |b| {
-        b.iter(|| make_and_fill_vec_prealloc::<N>())
+        b.iter(make_and_fill_vec_prealloc::<N>)
     });
     g.bench_function("SmallVec::with_capacity(N) + push N", |b| {
-        b.iter(|| make_and_fill_smallvec_prealloc::<N>())
+        b.iter(make_and_fill_smallvec_prealloc::<N>)
     });
     g.bench_function("TinyVec::with_capacity(N) + push N", |b| {
-        b.iter(|| make_and_fill_tinyvec_prealloc::<N>())
+        b.iter(make_and_fill_tinyvec_prealloc::<N>)
     });
 
     g.finish();
@@ -70,4 +93,4 @@ fn benches(c: &mut Criterion) {
 }
 
 criterion_group!(benches_group, benches);
-criterion_main!(benches_group);
+rust_examples::bench_main!(benches_group);
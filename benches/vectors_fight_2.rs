@@ -1,11 +1,19 @@
-use criterion::{BatchSize, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use criterion::measurement::WallTime;
+use criterion::{BatchSize, BenchmarkGroup, Criterion, Throughput, black_box, criterion_group};
+use rust_examples::alloc;
+use rust_examples::data::Gen;
 use smallvec::SmallVec;
+use std::sync::OnceLock;
 use tinyvec::TinyVec;
 
 // Tune to taste:
 const REPS: usize = 100_000; // how many tiny vectors we create & drop per iteration
 const K: usize = 8; // inline size / typical tiny size (keep <= K to avoid heap)
 
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
+
 // ---------- helpers ----------
 fn do_work_on(v: &[u32]) -> u64 {
     // Minimal use to prevent optimization-away.
@@ -65,17 +73,16 @@ fn many_tiny_tinyvec_fixed_k() -> u64 {
     acc
 }
 
-// Varied-size (0..=K) via tiny LCG — closer to “sometimes small”
-fn lcg_step(x: &mut u64) -> usize {
-    *x = x.wrapping_mul(6364136223846793005).wrapping_add(1);
-    ((*x >> 32) as usize) % (K + 1)
+// Varied-size (0..=K) — closer to “sometimes small”. Drawn once, so every
+// variant builds the same sequence of sizes.
+fn varied_sizes() -> &'static [usize] {
+    static SIZES: OnceLock<Vec<usize>> = OnceLock::new();
+    SIZES.get_or_init(|| Gen::new("vectors_fight_2/sizes").uniform(REPS, 0..=K))
 }
 
 fn many_tiny_vec_varied() -> u64 {
     let mut acc = 0u64;
-    let mut rnd = 0xDEADBEEFCAFEBABEu64;
-    for _ in 0..REPS {
-        let sz = lcg_step(&mut rnd);
+    for &sz in varied_sizes() {
         let mut v: Vec<u32> = Vec::new(); // alloc per tiny Vec
         for i in 0..(sz as u32) {
             v.push(black_box(i));
@@ -87,9 +94,7 @@ fn many_tiny_vec_varied() -> u64 {
 
 fn many_tiny_vec_varied_reserve() -> u64 {
     let mut acc = 0u64;
-    let mut rnd = 0xFACEFEED12345678u64;
-    for _ in 0..REPS {
-        let sz = lcg_step(&mut rnd);
+    for &sz in varied_sizes() {
         let mut v: Vec<u32> = Vec::with_capacity(K); // still alloc each time
         for i in 0..(sz as u32) {
             v.push(black_box(i));
@@ -101,9 +106,7 @@ fn many_tiny_vec_varied_reserve() -> u64 {
 
 fn many_tiny_smallvec_varied() -> u64 {
     let mut acc = 0u64;
-    let mut rnd = 0xA5A5A5A5A5A5A5A5u64;
-    for _ in 0..REPS {
-        let sz = lcg_step(&mut rnd);
+    for &sz in varied_sizes() {
         let mut v: SmallVec<[u32; K]> = SmallVec::new(); // inline for sz<=K
         for i in 0..(sz as u32) {
             v.push(black_box(i));
@@ -115,9 +118,7 @@ fn many_tiny_smallvec_varied() -> u64 {
 
 fn many_tiny_tinyvec_varied() -> u64 {
     let mut acc = 0u64;
-    let mut rnd = 0x0123456789ABCDEFu64;
-    for _ in 0..REPS {
-        let sz = lcg_step(&mut rnd);
+    for &sz in varied_sizes() {
         let mut v: TinyVec<[u32; K]> = TinyVec::new(); // inline for sz<=K
         for i in 0..(sz as u32) {
             v.push(black_box(i));
@@ -128,78 +129,75 @@ fn many_tiny_tinyvec_varied() -> u64 {
 }
 
 // ---------- benches ----------
+
+// A labelled bench body: (label, function run once per iteration).
+type Variant = (&'static str, fn() -> u64);
+
+// Variant labels double as claims: "noalloc" must not touch the heap and
+// "alloc per tiny vec" must allocate. Checked in `count-allocs` builds.
+fn check_alloc_labels(group: &str, variants: &[Variant]) {
+    if !alloc::ENABLED {
+        return;
+    }
+    for &(name, f) in variants {
+        let stats = alloc::report(&format!("{group}/{name}"), f);
+        if name.contains("noalloc") {
+            assert_eq!(stats.allocations, 0, "{group}/{name} allocated");
+        } else if name.contains("alloc per tiny vec") {
+            assert!(stats.allocations > 0, "{group}/{name} never allocated");
+        }
+    }
+}
+
+fn bench_variants(g: &mut BenchmarkGroup<'_, WallTime>, variants: &[Variant]) {
+    for &(name, f) in variants {
+        g.bench_function(name, |b| {
+            b.iter_batched(|| (), |_| black_box(f()), BatchSize::SmallInput)
+        });
+    }
+}
+
 fn bench_many_tiny_fixed(c: &mut Criterion) {
-    let mut g = c.benchmark_group(format!("many_tiny_fixed_K={}_REPS={}", K, REPS));
+    let group = format!("many_tiny_fixed_K={}_REPS={}", K, REPS);
+    let variants: [Variant; 4] = [
+        ("Vec (alloc per tiny vec)", many_tiny_vec_fixed_k),
+        (
+            "Vec reserve K (alloc per tiny vec)",
+            many_tiny_vec_fixed_k_reserve,
+        ),
+        (
+            "SmallVec<[K]> (inline, noalloc)",
+            many_tiny_smallvec_fixed_k,
+        ),
+        ("TinyVec<[K]> (inline, noalloc)", many_tiny_tinyvec_fixed_k),
+    ];
+    check_alloc_labels(&group, &variants);
+
+    let mut g = c.benchmark_group(group);
     // Total pushed elements per iteration: REPS * K
     g.throughput(Throughput::Elements((REPS as u64) * (K as u64)));
-
-    g.bench_function("Vec (alloc per tiny vec)", |b| {
-        b.iter_batched(
-            || (),
-            |_| black_box(many_tiny_vec_fixed_k()),
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("Vec reserve K (alloc per tiny vec)", |b| {
-        b.iter_batched(
-            || (),
-            |_| black_box(many_tiny_vec_fixed_k_reserve()),
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("SmallVec<[K]> (inline, noalloc)", |b| {
-        b.iter_batched(
-            || (),
-            |_| black_box(many_tiny_smallvec_fixed_k()),
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("TinyVec<[K]> (inline, noalloc)", |b| {
-        b.iter_batched(
-            || (),
-            |_| black_box(many_tiny_tinyvec_fixed_k()),
-            BatchSize::SmallInput,
-        )
-    });
-
+    bench_variants(&mut g, &variants);
     g.finish();
 }
 
 fn bench_many_tiny_varied(c: &mut Criterion) {
-    let mut g = c.benchmark_group(format!("many_tiny_varied_<=K K={}_REPS={}", K, REPS));
-    // Expected total pushes ≈ REPS * (average of 0..=K) = REPS * (K/2)
-    // Use u64 arithmetic first to avoid E0605 / trait errors.
-    g.throughput(Throughput::Elements((REPS as u64) * (K as u64) / 2));
-
-    g.bench_function("Vec (alloc per tiny vec)", |b| {
-        b.iter_batched(
-            || (),
-            |_| black_box(many_tiny_vec_varied()),
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("Vec reserve K (alloc per tiny vec)", |b| {
-        b.iter_batched(
-            || (),
-            |_| black_box(many_tiny_vec_varied_reserve()),
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("SmallVec<[K]> (inline, noalloc)", |b| {
-        b.iter_batched(
-            || (),
-            |_| black_box(many_tiny_smallvec_varied()),
-            BatchSize::SmallInput,
-        )
-    });
-    g.bench_function("TinyVec<[K]> (inline, noalloc)", |b| {
-        b.iter_batched(
-            || (),
-            |_| black_box(many_tiny_tinyvec_varied()),
-            BatchSize::SmallInput,
-        )
-    });
-
+    let group = format!("many_tiny_varied_<=K K={}_REPS={}", K, REPS);
+    let variants: [Variant; 4] = [
+        ("Vec (alloc per tiny vec)", many_tiny_vec_varied),
+        (
+            "Vec reserve K (alloc per tiny vec)",
+            many_tiny_vec_varied_reserve,
+        ),
+        ("SmallVec<[K]> (inline, noalloc)", many_tiny_smallvec_varied),
+        ("TinyVec<[K]> (inline, noalloc)", many_tiny_tinyvec_varied),
+    ];
+    // Draw the sizes before counting allocations, not inside the first variant.
+    let pushes: usize = varied_sizes().iter().sum();
+    check_alloc_labels(&group, &variants);
+
+    let mut g = c.benchmark_group(group);
+    g.throughput(Throughput::Elements(pushes as u64));
+    bench_variants(&mut g, &variants);
     g.finish();
 }
 
@@ -209,4 +207,4 @@ fn benches(c: &mut Criterion) {
 }
 
 criterion_group!(benches_group, benches);
-criterion_main!(benches_group);
+rust_examples::bench_main!(benches_group);
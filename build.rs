@@ -0,0 +1,136 @@
+//! Generates the payload structs for the struct-size sweep.
+//!
+//! `SWEEP_FIELDS` picks the field counts as a comma-separated list
+//! (default `1,2,4,8,16,32,64,128`). For each count N this writes `PodN`
+//! (N `u64` fields) and `MixedN` (scalars interleaved with `String` and
+//! `Vec<u8>` fields) to `$OUT_DIR/sweep.rs`, which `src/sweep.rs` includes.
+//!
+//! It also hands the build's opt-level, target features and `RUSTFLAGS` to
+//! the crate as `BENCH_*` variables, which bench binaries record when they
+//! run (see `results::BuildInfo`).
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const DEFAULT_FIELDS: &str = "1,2,4,8,16,32,64,128";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SWEEP_FIELDS");
+    println!("cargo:rerun-if-env-changed=RUSTFLAGS");
+    println!("cargo:rerun-if-env-changed=CARGO_ENCODED_RUSTFLAGS");
+
+    let var = |name: &str| env::var(name).unwrap_or_default();
+    println!("cargo:rustc-env=BENCH_OPT_LEVEL={}", var("OPT_LEVEL"));
+    println!(
+        "cargo:rustc-env=BENCH_TARGET_FEATURES={}",
+        var("CARGO_CFG_TARGET_FEATURE")
+    );
+    println!(
+        "cargo:rustc-env=BENCH_RUSTFLAGS={}",
+        var("CARGO_ENCODED_RUSTFLAGS").replace('\x1f', " ")
+    );
+
+    let spec = env::var("SWEEP_FIELDS").unwrap_or_else(|_| DEFAULT_FIELDS.to_string());
+    let counts: Vec<usize> = spec
+        .split(',')
+        .map(|n| match n.trim().parse() {
+            Ok(n) if n > 0 => n,
+            _ => panic!("SWEEP_FIELDS: `{n}` is not a positive field count"),
+        })
+        .collect();
+
+    let mut out = String::new();
+    for &n in &counts {
+        pod(&mut out, n);
+        mixed(&mut out, n);
+    }
+
+    let list: Vec<String> = counts.iter().map(usize::to_string).collect();
+    writeln!(out, "/// Field counts in this build, from `SWEEP_FIELDS`.").unwrap();
+    writeln!(
+        out,
+        "pub const FIELD_COUNTS: &[usize] = &[{}];\n",
+        list.join(", ")
+    )
+    .unwrap();
+
+    writeln!(
+        out,
+        "/// Calls `visitor` once per generated struct, in sweep order."
+    )
+    .unwrap();
+    writeln!(out, "pub fn visit_all<V: Visitor>(visitor: &mut V) {{").unwrap();
+    for &n in &counts {
+        writeln!(out, "    visitor.visit::<Pod{n}>(Layout::Pod);").unwrap();
+        writeln!(out, "    visitor.visit::<Mixed{n}>(Layout::Mixed);").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("sweep.rs");
+    fs::write(path, out).unwrap();
+}
+
+fn pod(out: &mut String, n: usize) {
+    writeln!(out, "#[derive(Clone, Debug)]\npub struct Pod{n} {{").unwrap();
+    for i in 0..n {
+        writeln!(out, "    pub f{i}: u64,").unwrap();
+    }
+    writeln!(out, "}}\n").unwrap();
+
+    writeln!(out, "impl Fixture for Pod{n} {{").unwrap();
+    writeln!(out, "    const FIELDS: usize = {n};\n").unwrap();
+    writeln!(
+        out,
+        "    fn build_with(size: usize, _map_len: usize) -> Self {{"
+    )
+    .unwrap();
+    writeln!(out, "        Pod{n} {{").unwrap();
+    for i in 0..n {
+        writeln!(out, "            f{i}: {},", scalar(i)).unwrap();
+    }
+    writeln!(out, "        }}\n    }}\n}}\n").unwrap();
+}
+
+/// Value for scalar field `i`, distinct per field so nothing folds away.
+fn scalar(i: usize) -> String {
+    match i {
+        0 => "size as u64".to_string(),
+        _ => format!("size as u64 + {i}"),
+    }
+}
+
+/// Even fields are `u64`; odd ones alternate between `String` and `Vec<u8>`.
+fn mixed(out: &mut String, n: usize) {
+    let ty = |i: usize| match i % 4 {
+        1 => "String",
+        3 => "Vec<u8>",
+        _ => "u64",
+    };
+    let value = |i: usize| match i % 4 {
+        1 => "text(size)".to_string(),
+        3 => "vec![0xAB; size]".to_string(),
+        _ => scalar(i),
+    };
+
+    writeln!(out, "#[derive(Clone, Debug)]\npub struct Mixed{n} {{").unwrap();
+    for i in 0..n {
+        writeln!(out, "    pub f{i}: {},", ty(i)).unwrap();
+    }
+    writeln!(out, "}}\n").unwrap();
+
+    writeln!(out, "impl Fixture for Mixed{n} {{").unwrap();
+    writeln!(out, "    const FIELDS: usize = {n};\n").unwrap();
+    writeln!(
+        out,
+        "    fn build_with(size: usize, _map_len: usize) -> Self {{"
+    )
+    .unwrap();
+    writeln!(out, "        Mixed{n} {{").unwrap();
+    for i in 0..n {
+        writeln!(out, "            f{i}: {},", value(i)).unwrap();
+    }
+    writeln!(out, "        }}\n    }}\n}}\n").unwrap();
+}
@@ -0,0 +1,159 @@
+//! A counting `GlobalAlloc` wrapper for checking allocation claims.
+//!
+//! The counters put atomic updates on every allocation, which would skew
+//! exactly the alloc-vs-noalloc gaps the benches time, so they are only
+//! installed with `--features count-allocs`:
+//!
+//! ```text
+//! #[cfg(feature = "count-allocs")]
+//! #[global_allocator]
+//! static GLOBAL: alloc::CountingAlloc = alloc::CountingAlloc;
+//! ```
+//!
+//! A bench checks its claims under `if alloc::ENABLED`, wrapping the code
+//! under test in [`measure`] or [`report`]. The counters are process-wide, so
+//! measure outside of Criterion's timing loop (which allocates on its own) by
+//! calling the bench body directly. A `count-allocs` build refuses to time
+//! anything (see [`intercept`]); run it with `-- --test`, or through
+//! `run-suite run --count-allocs`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+/// Whether this build counts allocations and checks the benches' claims.
+pub const ENABLED: bool = cfg!(feature = "count-allocs");
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static DEALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static REALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Forwards to [`System`] and counts every call.
+pub struct CountingAlloc;
+
+fn grow_live(bytes: u64) {
+    let live = LIVE_BYTES.fetch_add(bytes, Relaxed) + bytes;
+    PEAK_LIVE_BYTES.fetch_max(live, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            BYTES_ALLOCATED.fetch_add(layout.size() as u64, Relaxed);
+            grow_live(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            BYTES_ALLOCATED.fetch_add(layout.size() as u64, Relaxed);
+            grow_live(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        DEALLOCATIONS.fetch_add(1, Relaxed);
+        LIVE_BYTES.fetch_sub(layout.size() as u64, Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            REALLOCATIONS.fetch_add(1, Relaxed);
+            let (old, new) = (layout.size() as u64, new_size as u64);
+            if new > old {
+                BYTES_ALLOCATED.fetch_add(new - old, Relaxed);
+                grow_live(new - old);
+            } else {
+                LIVE_BYTES.fetch_sub(old - new, Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Allocator activity over one measured region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Calls to `alloc`/`alloc_zeroed`.
+    pub allocations: u64,
+    pub deallocations: u64,
+    pub reallocations: u64,
+    /// Bytes handed out by `alloc`, plus the grown part of each `realloc`.
+    pub bytes_allocated: u64,
+    /// Highest live-byte count reached, relative to the start of the region.
+    pub peak_live_bytes: u64,
+}
+
+/// Runs `f` and returns its result together with the allocator activity it caused.
+///
+/// Panics if [`CountingAlloc`] is not the global allocator, since every
+/// count would silently read zero otherwise.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    assert_installed();
+
+    let live = LIVE_BYTES.load(Relaxed);
+    PEAK_LIVE_BYTES.store(live, Relaxed);
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let deallocations = DEALLOCATIONS.load(Relaxed);
+    let reallocations = REALLOCATIONS.load(Relaxed);
+    let bytes = BYTES_ALLOCATED.load(Relaxed);
+
+    let out = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        deallocations: DEALLOCATIONS.load(Relaxed) - deallocations,
+        reallocations: REALLOCATIONS.load(Relaxed) - reallocations,
+        bytes_allocated: BYTES_ALLOCATED.load(Relaxed) - bytes,
+        peak_live_bytes: PEAK_LIVE_BYTES.load(Relaxed).saturating_sub(live),
+    };
+    (out, stats)
+}
+
+/// Runs one iteration of a bench body, prints its allocation profile and
+/// returns it so the caller can assert on it.
+pub fn report<R>(label: &str, f: impl FnOnce() -> R) -> AllocStats {
+    let (out, stats) = measure(f);
+    black_box(out);
+    println!(
+        "allocs {label}: allocations={} reallocations={} bytes={} peak_live={}",
+        stats.allocations, stats.reallocations, stats.bytes_allocated, stats.peak_live_bytes
+    );
+    stats
+}
+
+fn assert_installed() {
+    let before = ALLOCATIONS.load(Relaxed);
+    drop(black_box(Box::new(0u64)));
+    assert!(
+        ALLOCATIONS.load(Relaxed) > before,
+        "CountingAlloc is not installed as the #[global_allocator]"
+    );
+}
+
+/// Called by [`bench_main!`](crate::bench_main) before any group runs.
+///
+/// A `count-allocs` build asked to time benches, rather than run each once
+/// in `--test` mode, exits with an error. Otherwise returns `false` and lets
+/// Criterion run.
+pub fn intercept() -> bool {
+    if ENABLED && !std::env::args().any(|a| a == "--test" || a == "--list") {
+        eprintln!(
+            "count-allocs: counting slows every allocation down, so this build only \
+             checks allocation claims; rerun with `-- --test`"
+        );
+        std::process::exit(1);
+    }
+    false
+}
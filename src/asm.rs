@@ -0,0 +1,591 @@
+//! Emits a bench target's assembly and checks codegen claims against it.
+//!
+//! Several benches explain their timings with codegen arguments ("forces
+//! sret + one copy", "forwarding ownership costs nothing"). [`emit`] builds a
+//! target with `--emit asm` and v0 symbol mangling, so every const-generic
+//! instantiation is its own readable symbol, [`parse`] splits the output into
+//! functions, and [`EXPECTATIONS`] turns those comments into assertions.
+//! [`stats`] also counts packed SIMD arithmetic, i.e. whether a loop was
+//! vectorised.
+
+use rustc_demangle::demangle;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Output kinds `rustc --emit` can produce for inspection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Asm,
+    LlvmIr,
+}
+
+impl Format {
+    fn emit(self) -> &'static str {
+        match self {
+            Format::Asm => "asm",
+            Format::LlvmIr => "llvm-ir",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Asm => "s",
+            Format::LlvmIr => "ll",
+        }
+    }
+}
+
+/// One function from the emitted output.
+#[derive(Clone, Debug)]
+pub struct Function {
+    /// Demangled path without hashes, e.g. `array::id_return_noinline::<1000>`.
+    pub name: String,
+    /// Body lines, label and directives included.
+    pub body: Vec<String>,
+}
+
+/// Codegen facts about one assembly function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Real instructions, not labels or assembler directives.
+    pub instructions: usize,
+    /// `memcpy`/`memmove` calls, tail calls included.
+    pub memcpy_calls: usize,
+    /// Instructions reading or writing through `%rsp`/`%rbp`, plus pushes and pops.
+    pub stack_ops: usize,
+    /// Bytes reserved with `sub $N, %rsp`.
+    pub frame_bytes: usize,
+    /// Packed (multi-lane) SIMD adds, subtracts, multiplies and FMAs; zero
+    /// means the function was not vectorised.
+    pub packed_ops: usize,
+}
+
+/// Builds `target` with the bench profile and returns the emitted file.
+///
+/// Uses its own target directory so the flags do not invalidate the
+/// regular bench build.
+pub fn emit(manifest_dir: &Path, target: &str, format: Format) -> Result<PathBuf, String> {
+    emit_profile(manifest_dir, target, format, "bench", &[])
+}
+
+/// Like [`emit`], with another cargo profile and extra flags for the target
+/// itself. The binary is linked too: it is the emitted file without its
+/// extension.
+pub fn emit_profile(
+    manifest_dir: &Path,
+    target: &str,
+    format: Format,
+    profile: &str,
+    rustc_args: &[&str],
+) -> Result<PathBuf, String> {
+    let target_dir = manifest_dir.join("target").join("asm");
+    let mut cmd = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cmd.current_dir(manifest_dir)
+        .args(["rustc", "-q", "--profile", profile, "--bench", target])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .args([
+            "--",
+            "--emit",
+            &format!("{},link", format.emit()),
+            "-C",
+            "symbol-mangling-version=v0",
+        ])
+        .args(rustc_args);
+    let status = cmd
+        .status()
+        .map_err(|e| format!("failed to start cargo rustc: {e}"))?;
+    if !status.success() {
+        return Err(format!("cargo rustc for `{target}` exited with {status}"));
+    }
+
+    // rustc names the file after the crate plus its metadata hash; after a
+    // rebuild the newest one is ours.
+    let profile_dir = match profile {
+        "bench" | "release" => "release",
+        "dev" | "test" => "debug",
+        custom => custom,
+    };
+    let deps = target_dir.join(profile_dir).join("deps");
+    let prefix = format!("{target}-");
+    let entries =
+        fs::read_dir(&deps).map_err(|e| format!("cannot read {}: {e}", deps.display()))?;
+    entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension().is_some_and(|x| x == format.extension())
+                && p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(&prefix))
+        })
+        .max_by_key(|p| fs::metadata(p).and_then(|m| m.modified()).ok())
+        .ok_or_else(|| {
+            format!(
+                "no .{} file for `{target}` in {}",
+                format.extension(),
+                deps.display()
+            )
+        })
+}
+
+/// Splits emitted assembly or LLVM IR into functions.
+pub fn parse(text: &str, format: Format) -> Vec<Function> {
+    let mut out = Vec::new();
+    let mut current: Option<Function> = None;
+
+    for line in text.lines() {
+        if let Some(f) = current.as_mut() {
+            f.body.push(line.to_string());
+            let end = match format {
+                Format::Asm => line.starts_with(".Lfunc_end"),
+                Format::LlvmIr => line == "}",
+            };
+            if end {
+                out.extend(current.take());
+            }
+            continue;
+        }
+        let symbol = match format {
+            Format::Asm => line.strip_suffix(':').filter(|s| is_mangled(s)),
+            Format::LlvmIr => line
+                .strip_prefix("define ")
+                .and_then(|l| l.split_once('@'))
+                .and_then(|(_, rest)| rest.split_once('('))
+                .map(|(sym, _)| sym.trim_matches('"'))
+                .filter(|s| is_mangled(s)),
+        };
+        if let Some(symbol) = symbol {
+            current = Some(Function {
+                name: format!("{:#}", demangle(symbol)),
+                body: vec![line.to_string()],
+            });
+        }
+    }
+    out
+}
+
+fn is_mangled(symbol: &str) -> bool {
+    symbol.starts_with("_R") || symbol.starts_with("_ZN")
+}
+
+/// Counts copies and stack traffic in an assembly function.
+pub fn stats(function: &Function) -> Stats {
+    let mut s = Stats::default();
+    for line in &function.body {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('.') || line.ends_with(':') || line.starts_with('#')
+        {
+            continue;
+        }
+        s.instructions += 1;
+        let mnemonic = line.split_whitespace().next().unwrap_or("");
+        let is_call = mnemonic.starts_with("call") || mnemonic.starts_with("jmp");
+        if is_call && (line.contains("memcpy") || line.contains("memmove")) {
+            s.memcpy_calls += 1;
+        }
+        if line.contains("(%rsp)")
+            || line.contains("(%rbp)")
+            || mnemonic.starts_with("push")
+            || mnemonic.starts_with("pop")
+        {
+            s.stack_ops += 1;
+        }
+        if let Some(bytes) = mnemonic
+            .starts_with("sub")
+            .then(|| line.strip_suffix(", %rsp"))
+            .flatten()
+            .and_then(|l| l.split('$').nth(1))
+            .and_then(|n| n.parse::<usize>().ok())
+        {
+            s.frame_bytes += bytes;
+        }
+        if is_packed_arith(mnemonic, line) {
+            s.packed_ops += 1;
+        }
+    }
+    s
+}
+
+/// Whether an instruction does arithmetic on several SIMD lanes at once.
+/// `%xmm` registers also carry scalar floats (`addss`, `mulsd`), which do
+/// not count.
+fn is_packed_arith(mnemonic: &str, line: &str) -> bool {
+    let arith = ["add", "sub", "mul", "fma"]
+        .iter()
+        .any(|op| mnemonic.contains(op));
+    let wide = line.contains("%ymm") || line.contains("%zmm");
+    let scalar = mnemonic.ends_with("ss") || mnemonic.ends_with("sd");
+    arith && (wide || (line.contains("%xmm") && !scalar))
+}
+
+/// Whether `name` (a demangled path in crate `target`) is `wanted`.
+///
+/// `wanted` is a path inside the crate; without generic arguments it
+/// matches every instantiation.
+pub fn matches(name: &str, target: &str, wanted: &str) -> bool {
+    let Some(path) = name.strip_prefix(target).and_then(|p| p.strip_prefix("::")) else {
+        return false;
+    };
+    path == wanted
+        || path
+            .strip_prefix(wanted)
+            .is_some_and(|rest| rest.starts_with("::<"))
+}
+
+/// Whether `source` defines the function `wanted` names (a path as in
+/// [`matches`]; only its last segment is looked up).
+pub fn defines(source: &str, wanted: &str) -> bool {
+    let path = wanted.split("::<").next().unwrap_or(wanted);
+    let name = path.rsplit("::").next().unwrap_or(path);
+    let needle = format!("fn {name}");
+    source.match_indices(&needle).any(|(at, _)| {
+        let rest = &source[at + needle.len()..];
+        rest.starts_with('<') || rest.starts_with('(')
+    })
+}
+
+// -----------------------------
+// Expectations
+// -----------------------------
+
+/// What a bench's comments promise about one function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// No out-of-line copy of the function survives. The function must
+    /// still be defined in the bench source, so a rename cannot pass it.
+    Inlined,
+    /// The function is emitted and calls `memcpy` exactly this many times.
+    MemcpyCalls(usize),
+    /// The function is emitted and reserves at most this many stack bytes.
+    FrameBytesAtMost(usize),
+    /// The function is emitted and loads nothing from a `.Lswitch.table`,
+    /// i.e. no `match` in it was folded into a table of constants.
+    NoSwitchTable,
+}
+
+#[derive(Debug)]
+pub struct Expectation {
+    pub target: &'static str,
+    /// Path inside the target crate, optionally with generic arguments.
+    pub function: &'static str,
+    pub check: Check,
+    /// The comment or claim being checked.
+    pub claim: &'static str,
+}
+
+const fn expect(
+    target: &'static str,
+    function: &'static str,
+    check: Check,
+    claim: &'static str,
+) -> Expectation {
+    Expectation {
+        target,
+        function,
+        check,
+        claim,
+    }
+}
+
+const SRET_COPY: &str = "return-by-value identity forces sret + one copy";
+const MANUAL_COPY: &str = "manual copy is a single memcpy";
+const BUILT_IN_SRET: &str = "constructed in caller's sret (no extra copy)";
+const NO_CALLER_COPY: &str = "the caller holds a single buffer and copies nothing";
+const REAL_DISPATCH: &str = "the enum match dispatches to inlined bodies, not a table lookup";
+const FORWARDING: &str = "forwarding ownership through the layers costs nothing";
+
+pub const EXPECTATIONS: &[Expectation] = &[
+    // array: large arrays go through memcpy, small ones through registers.
+    expect(
+        "array",
+        "id_return_noinline::<1000>",
+        Check::MemcpyCalls(1),
+        SRET_COPY,
+    ),
+    expect(
+        "array",
+        "id_return_noinline::<2048>",
+        Check::MemcpyCalls(1),
+        SRET_COPY,
+    ),
+    expect(
+        "array",
+        "manual_memcpy_noinline::<1000>",
+        Check::MemcpyCalls(1),
+        MANUAL_COPY,
+    ),
+    expect(
+        "array",
+        "manual_memcpy_noinline::<2048>",
+        Check::MemcpyCalls(1),
+        MANUAL_COPY,
+    ),
+    expect(
+        "array",
+        "build_return_inline",
+        Check::Inlined,
+        BUILT_IN_SRET,
+    ),
+    expect(
+        "array",
+        "build_outparam_inline",
+        Check::Inlined,
+        BUILT_IN_SRET,
+    ),
+    // The builders' callers: no memcpy and one N-byte buffer on the stack
+    // (plus alignment), where a copy would need two.
+    expect(
+        "array",
+        "run_build_return::<1000>",
+        Check::MemcpyCalls(0),
+        NO_CALLER_COPY,
+    ),
+    expect(
+        "array",
+        "run_build_return::<1000>",
+        Check::FrameBytesAtMost(1000 + 64),
+        NO_CALLER_COPY,
+    ),
+    expect(
+        "array",
+        "run_build_return::<2048>",
+        Check::MemcpyCalls(0),
+        NO_CALLER_COPY,
+    ),
+    expect(
+        "array",
+        "run_build_return::<2048>",
+        Check::FrameBytesAtMost(2048 + 64),
+        NO_CALLER_COPY,
+    ),
+    expect(
+        "array",
+        "run_build_outparam::<1000>",
+        Check::MemcpyCalls(0),
+        NO_CALLER_COPY,
+    ),
+    expect(
+        "array",
+        "run_build_outparam::<1000>",
+        Check::FrameBytesAtMost(1000 + 64),
+        NO_CALLER_COPY,
+    ),
+    expect(
+        "array",
+        "run_build_outparam::<2048>",
+        Check::MemcpyCalls(0),
+        NO_CALLER_COPY,
+    ),
+    expect(
+        "array",
+        "run_build_outparam::<2048>",
+        Check::FrameBytesAtMost(2048 + 64),
+        NO_CALLER_COPY,
+    ),
+    // layers_move
+    expect("layers_move", "one_layer_p", Check::Inlined, FORWARDING),
+    expect("layers_move", "repo_p", Check::Inlined, FORWARDING),
+    expect("layers_move", "usecase_p", Check::Inlined, FORWARDING),
+    expect("layers_move", "controller_p", Check::Inlined, FORWARDING),
+    expect("layers_move", "one_layer_b", Check::Inlined, FORWARDING),
+    expect("layers_move", "repo_b", Check::Inlined, FORWARDING),
+    expect("layers_move", "usecase_b", Check::Inlined, FORWARDING),
+    expect("layers_move", "controller_b", Check::Inlined, FORWARDING),
+    // trait_direct
+    expect(
+        "trait_direct",
+        "sum_enum",
+        Check::NoSwitchTable,
+        REAL_DISPATCH,
+    ),
+];
+
+/// Expectations registered for `target`.
+pub fn expectations(target: &str) -> impl Iterator<Item = &'static Expectation> {
+    EXPECTATIONS.iter().filter(move |e| e.target == target)
+}
+
+impl Expectation {
+    /// Checks this expectation against a target's parsed assembly and its
+    /// source; the error describes what was found instead.
+    pub fn verify(&self, functions: &[Function], source: &str) -> Result<(), String> {
+        let found: Vec<&Function> = functions
+            .iter()
+            .filter(|f| matches(&f.name, self.target, self.function))
+            .collect();
+        match self.check {
+            Check::Inlined if !defines(source, self.function) => {
+                Err("function not defined in the bench source (renamed?)".to_string())
+            }
+            Check::Inlined if found.is_empty() => Ok(()),
+            Check::Inlined => Err(format!(
+                "expected no out-of-line copy, found {}",
+                found
+                    .iter()
+                    .map(|f| f.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Check::MemcpyCalls(_) | Check::FrameBytesAtMost(_) | Check::NoSwitchTable
+                if found.is_empty() =>
+            {
+                Err("function not emitted (inlined or renamed?)".to_string())
+            }
+            Check::MemcpyCalls(n) => {
+                let bad: Vec<String> = found
+                    .iter()
+                    .map(|f| (f, stats(f).memcpy_calls))
+                    .filter(|(_, calls)| *calls != n)
+                    .map(|(f, calls)| format!("{} has {calls}", f.name))
+                    .collect();
+                if bad.is_empty() {
+                    Ok(())
+                } else {
+                    Err(format!("expected {n} memcpy call(s); {}", bad.join(", ")))
+                }
+            }
+            Check::FrameBytesAtMost(n) => {
+                let bad: Vec<String> = found
+                    .iter()
+                    .map(|f| (f, stats(f).frame_bytes))
+                    .filter(|(_, bytes)| *bytes > n)
+                    .map(|(f, bytes)| format!("{} reserves {bytes}", f.name))
+                    .collect();
+                if bad.is_empty() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "expected at most {n} frame bytes; {}",
+                        bad.join(", ")
+                    ))
+                }
+            }
+            Check::NoSwitchTable => {
+                let bad: Vec<&str> = found
+                    .iter()
+                    .filter(|f| f.body.iter().any(|l| l.contains(".Lswitch.table")))
+                    .map(|f| f.name.as_str())
+                    .collect();
+                if bad.is_empty() {
+                    Ok(())
+                } else {
+                    Err(format!("switch table lookup in {}", bad.join(", ")))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASM: &str = "\
+\t.section\t.text._ZN5array4copy17h0123456789abcdefE,\"ax\",@progbits
+_ZN5array4copy17h0123456789abcdefE:
+\t.cfi_startproc
+\tpushq\t%rbx
+\tsubq\t$2048, %rsp
+\tmovq\t%rdi, 8(%rsp)
+\tcallq\t*memcpy@GOTPCREL(%rip)
+\tvpaddd\t%ymm0, %ymm1, %ymm2
+\taddss\t%xmm0, %xmm1
+.LBB0_1:
+\tpopq\t%rbx
+\tretq
+.Lfunc_end0:
+\t.size\t_ZN5array4copy17h0123456789abcdefE, .Lfunc_end0-_ZN5array4copy17h0123456789abcdefE
+_ZN5array4tail17h0123456789abcdefE:
+\tjmp\tmemmove@PLT
+.Lfunc_end1:
+";
+
+    #[test]
+    fn parse_splits_asm_into_functions() {
+        let functions = parse(ASM, Format::Asm);
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["array::copy", "array::tail"]);
+        assert_eq!(functions[1].body.len(), 3);
+    }
+
+    #[test]
+    fn parse_splits_llvm_ir_into_functions() {
+        let ir = "\
+define internal void @_ZN5array4copy17h0123456789abcdefE(ptr %0) {
+start:
+  ret void
+}
+declare void @llvm.memcpy.p0.p0.i64(ptr, ptr, i64, i1)
+";
+        let functions = parse(ir, Format::LlvmIr);
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "array::copy");
+        assert_eq!(functions[0].body.last().unwrap(), "}");
+    }
+
+    #[test]
+    fn stats_count_copies_stack_and_simd() {
+        let functions = parse(ASM, Format::Asm);
+        assert_eq!(
+            stats(&functions[0]),
+            Stats {
+                instructions: 8,
+                memcpy_calls: 1,
+                stack_ops: 3,
+                frame_bytes: 2048,
+                packed_ops: 1,
+            }
+        );
+        assert_eq!(stats(&functions[1]).memcpy_calls, 1);
+    }
+
+    #[test]
+    fn matches_takes_every_instantiation_without_generics() {
+        let name = "array::id_return_noinline::<1000>";
+        assert!(matches(name, "array", "id_return_noinline"));
+        assert!(matches(name, "array", "id_return_noinline::<1000>"));
+        assert!(!matches(name, "array", "id_return_noinline::<100>"));
+        assert!(!matches(name, "array", "id_return"));
+        assert!(!matches(name, "layers_move", "id_return_noinline"));
+    }
+
+    #[test]
+    fn inlined_needs_the_function_in_the_source() {
+        let source = "#[inline(always)]\nfn build<const N: usize>() {}\nfn builder() {}\n";
+        assert!(defines(source, "build::<8>"));
+        assert!(defines(source, "builder"));
+        assert!(!defines(source, "buil"));
+
+        let check = |function| expect("array", function, Check::Inlined, "");
+        let functions = parse(ASM, Format::Asm);
+        assert!(check("build").verify(&functions, source).is_ok());
+        assert!(check("biuld").verify(&functions, source).is_err());
+        let source = "fn copy() {}";
+        assert!(check("copy").verify(&functions, source).is_err());
+    }
+
+    #[test]
+    fn emitted_checks_fail_for_missing_functions() {
+        let functions = parse(ASM, Format::Asm);
+        let verify = |function, check| expect("array", function, check, "").verify(&functions, "");
+        assert!(verify("copy", Check::MemcpyCalls(1)).is_ok());
+        assert!(verify("copy", Check::MemcpyCalls(0)).is_err());
+        assert!(verify("copy", Check::FrameBytesAtMost(2048)).is_ok());
+        assert!(verify("copy", Check::FrameBytesAtMost(1024)).is_err());
+        assert!(verify("gone", Check::MemcpyCalls(0)).is_err());
+        assert!(verify("gone", Check::FrameBytesAtMost(0)).is_err());
+        assert!(verify("copy", Check::NoSwitchTable).is_ok());
+        assert!(verify("gone", Check::NoSwitchTable).is_err());
+
+        let table = ASM.replace(
+            "\tretq",
+            "\tleaq\t.Lswitch.table._ZN5array4copy17h0123456789abcdefE(%rip), %rcx\n\tretq",
+        );
+        let functions = parse(&table, Format::Asm);
+        let check = expect("array", "copy", Check::NoSwitchTable, "");
+        assert!(check.verify(&functions, "").is_err());
+    }
+}
@@ -0,0 +1,614 @@
+//! Lists, filters and runs the bench suites registered in `rust_examples::suites`.
+//!
+//! ```text
+//! run-suite list [SUITE|BENCH]...
+//! run-suite run  [SUITE|BENCH]... [--filter TEXT] [--cachegrind] [--count-allocs] [--native] [--dry-run] [-- CRITERION_ARGS...]
+//! run-suite export [--format json|csv] [--out PATH]
+//! run-suite compare [SUITE|BENCH]... (--baseline NAME | --snapshot PATH) [--threshold BENCH=PCT]...
+//! run-suite report [SUITE|BENCH]... [--snapshot PATH] [--out DIR]
+//! run-suite asm BENCH [FUNCTION]... [--ir] [--show] [--check]
+//! run-suite size [SUITE|BENCH]...
+//! ```
+
+use rust_examples::asm::{self, Format};
+use rust_examples::compare::{self, Comparison, Verdict};
+use rust_examples::report;
+use rust_examples::results::{self, BenchResult, Environment, Run};
+use rust_examples::size::{self, Footprint};
+use rust_examples::suites::{self, Bench, Suite};
+use rust_examples::vectorise;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+const USAGE: &str = "\
+usage: run-suite <command> [args]
+
+commands:
+  list [SUITE|BENCH]...     show suites and their bench targets
+  run  [SUITE|BENCH]...     run the selected targets (default: all)
+       --filter TEXT        keep only targets whose name contains TEXT
+       --cachegrind         count instructions under cachegrind instead of timing
+       --count-allocs       check allocation claims once per bench instead of timing
+       --native             build for this machine's CPU (bench-native profile plus
+                            RUSTFLAGS=-C target-cpu=native)
+       --dry-run            print the cargo command instead of running it
+       -- ARGS...           pass ARGS through to Criterion
+  export                    collect target/criterion results into one file
+       --format json|csv    output format (default: json)
+       --out PATH           where to write (default: target/results/<time>-<commit>.<ext>)
+  compare [SUITE|BENCH]...  compare the latest run against a baseline; fails on regressions
+       --baseline NAME      a Criterion baseline saved with --save-baseline NAME
+       --snapshot PATH      a JSON file written by `export`
+       --threshold BENCH=PCT  override a target's regression threshold
+  report [SUITE|BENCH]...   write a markdown findings page per experiment (plus which
+                            kernels were vectorised, for SIMD targets)
+       --snapshot PATH      use a JSON file written by `export` instead of the latest run
+       --out DIR            where to write (default: target/report)
+  asm BENCH [FUNCTION]...   count memcpy calls, stack traffic and SIMD ops in a target's functions
+       --ir                 inspect LLVM IR instead of assembly
+       --show               print the function bodies
+       --check              verify the target's codegen expectations; fails on mismatch
+  size [SUITE|BENCH]...     compare binary size and inlining under panic=unwind and panic=abort
+  help                      show this message
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some((command, rest)) = args.split_first() else {
+        eprint!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let result = match command.as_str() {
+        "list" => list(rest),
+        "run" => run(rest),
+        "export" => export(rest),
+        "compare" => compare(rest),
+        "report" => write_report(rest),
+        "asm" => inspect_asm(rest),
+        "size" => compare_size(rest),
+        "help" | "-h" | "--help" => {
+            print!("{USAGE}");
+            Ok(())
+        }
+        other => Err(format!("unknown command `{other}`\n\n{USAGE}")),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// -----------------------------
+// Commands
+// -----------------------------
+
+fn list(args: &[String]) -> Result<(), String> {
+    let selected = select(args)?;
+    for suite in Suite::ALL {
+        let benches: Vec<&Bench> = selected
+            .iter()
+            .copied()
+            .filter(|b| b.suite == suite)
+            .collect();
+        if benches.is_empty() {
+            continue;
+        }
+        println!("{suite}");
+        for b in benches {
+            println!("  {}", b.name);
+        }
+    }
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (args, passthrough) = split_passthrough(args);
+    let mut names = Vec::new();
+    let mut filter = None;
+    let mut cachegrind = false;
+    let mut count_allocs = false;
+    let mut native = false;
+    let mut dry_run = false;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--filter" => filter = Some(it.next().ok_or("--filter needs a value")?.clone()),
+            "--cachegrind" => cachegrind = true,
+            "--count-allocs" => count_allocs = true,
+            "--native" => native = true,
+            "--dry-run" => dry_run = true,
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{flag}`")),
+            name => names.push(name.to_string()),
+        }
+    }
+
+    let mut selected = select(&names)?;
+    if let Some(filter) = &filter {
+        selected.retain(|b| b.name.contains(filter.as_str()));
+    }
+    if selected.is_empty() {
+        return Err("no bench targets match the selection".to_string());
+    }
+
+    let mut cmd = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cmd.arg("bench");
+    if cachegrind && count_allocs {
+        return Err("--cachegrind and --count-allocs cannot be combined".to_string());
+    }
+    if cachegrind {
+        cmd.args(["--features", "cachegrind"]);
+    }
+    if count_allocs {
+        cmd.args(["--features", "count-allocs"]);
+    }
+    if native {
+        // A profile cannot set `target-cpu`, so it goes in through RUSTFLAGS,
+        // after whatever the caller already set.
+        let build = vectorise::NATIVE;
+        cmd.args(["--profile", build.profile]);
+        if let Some(cpu) = build.target_cpu {
+            let mut flags = env::var("RUSTFLAGS").unwrap_or_default();
+            if !flags.is_empty() {
+                flags.push(' ');
+            }
+            flags.push_str(&format!("-C target-cpu={cpu}"));
+            cmd.env("RUSTFLAGS", flags);
+        }
+    }
+    for b in &selected {
+        cmd.arg("--bench").arg(b.name);
+    }
+    let mut passthrough = passthrough.to_vec();
+    if count_allocs && !passthrough.iter().any(|a| a == "--test") {
+        passthrough.push("--test".to_string());
+    }
+    if !passthrough.is_empty() {
+        cmd.arg("--").args(passthrough);
+    }
+
+    if dry_run {
+        println!("{}", render(&cmd));
+        return Ok(());
+    }
+
+    let status = cmd
+        .status()
+        .map_err(|e| format!("failed to start `{}`: {e}", render(&cmd)))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("`{}` exited with {status}", render(&cmd)))
+    }
+}
+
+fn export(args: &[String]) -> Result<(), String> {
+    let mut format = "json".to_string();
+    let mut out = None;
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--format" => format = it.next().ok_or("--format needs a value")?.clone(),
+            "--out" => out = Some(PathBuf::from(it.next().ok_or("--out needs a value")?)),
+            other => return Err(format!("unknown argument `{other}`")),
+        }
+    }
+    if format != "json" && format != "csv" {
+        return Err(format!("unknown format `{format}` (expected json or csv)"));
+    }
+
+    let root = manifest_dir();
+    let dir = results::criterion_dir(root);
+    let results = results::collect(&dir)?;
+    if results.is_empty() {
+        return Err(format!(
+            "no results under {} (run the benches first)",
+            dir.display()
+        ));
+    }
+    let run = Run {
+        environment: Environment::capture(root, &dir),
+        results,
+    };
+
+    let out = out.unwrap_or_else(|| {
+        let commit = run.environment.git_commit.as_deref().unwrap_or("unknown");
+        let name = format!(
+            "{}-{}.{format}",
+            run.environment.exported_at,
+            &commit[..commit.len().min(12)]
+        );
+        root.join("target").join("results").join(name)
+    });
+    let body = match format.as_str() {
+        "csv" => run.to_csv(),
+        _ => run.to_json(),
+    };
+    if let Some(parent) = out.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("cannot create {}: {e}", parent.display()))?;
+    }
+    fs::write(&out, body).map_err(|e| format!("cannot write {}: {e}", out.display()))?;
+    println!("wrote {} results to {}", run.results.len(), out.display());
+    Ok(())
+}
+
+fn compare(args: &[String]) -> Result<(), String> {
+    let mut names = Vec::new();
+    let mut baseline = None;
+    let mut snapshot = None;
+    let mut overrides: Vec<(String, f64)> = Vec::new();
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--baseline" => baseline = Some(it.next().ok_or("--baseline needs a value")?.clone()),
+            "--snapshot" => {
+                snapshot = Some(PathBuf::from(it.next().ok_or("--snapshot needs a value")?))
+            }
+            "--threshold" => {
+                let value = it.next().ok_or("--threshold needs BENCH=PCT")?;
+                let (name, pct) = value
+                    .split_once('=')
+                    .ok_or_else(|| format!("`{value}` is not BENCH=PCT"))?;
+                suites::bench(name).ok_or_else(|| format!("unknown bench target `{name}`"))?;
+                let pct = pct
+                    .trim_end_matches('%')
+                    .parse()
+                    .map_err(|e| format!("bad threshold `{pct}`: {e}"))?;
+                overrides.push((name.to_string(), pct));
+            }
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{flag}`")),
+            name => names.push(name.to_string()),
+        }
+    }
+
+    let dir = results::criterion_dir(manifest_dir());
+    let base: Vec<BenchResult> = match (baseline, snapshot) {
+        (Some(name), None) => results::collect_baseline(&dir, &name)?,
+        (None, Some(path)) => read_snapshot(&path)?,
+        _ => return Err("pass exactly one of --baseline NAME or --snapshot PATH".to_string()),
+    };
+    let current = results::collect(&dir)?;
+
+    // Criterion ids do not say which target produced them, so ask each
+    // selected target for its list.
+    let mut owners: Vec<(String, &Bench)> = Vec::new();
+    for b in select(&names)? {
+        for id in list_ids(b)? {
+            owners.push((id, b));
+        }
+    }
+    let threshold = |id: &str| {
+        let (_, b) = owners.iter().find(|(owned, _)| owned == id)?;
+        let custom = overrides.iter().rev().find(|(name, _)| name == b.name);
+        Some(custom.map_or(b.threshold, |(_, pct)| *pct))
+    };
+
+    let report = compare::compare(&base, &current, threshold);
+    let target_of = |id: &str| {
+        owners
+            .iter()
+            .find(|(owned, _)| owned == id)
+            .map_or("?", |(_, b)| b.name)
+    };
+    let flagged: Vec<&Comparison> = report
+        .comparisons
+        .iter()
+        .filter(|c| c.verdict != Verdict::Unchanged)
+        .collect();
+    if !flagged.is_empty() {
+        println!(
+            "{:<10} {:<20} {:<40} {:>36} {:>36} {:>9} {:>6}",
+            "verdict", "target", "benchmark", "baseline", "current", "change", "limit"
+        );
+        for c in flagged {
+            println!(
+                "{:<10} {:<20} {:<40} {:>36} {:>36} {:>+8.2}% {:>5}%",
+                c.verdict,
+                target_of(&c.id),
+                c.id,
+                compare::format_interval(&c.id, &c.baseline),
+                compare::format_interval(&c.id, &c.current),
+                c.change,
+                c.threshold
+            );
+        }
+        println!();
+    }
+
+    let regressions = report.regressions().count();
+    println!(
+        "{} compared: {} regressed, {} improved, {} unchanged; {} new, {} missing from this run",
+        report.comparisons.len(),
+        regressions,
+        report.improvements().count(),
+        report.comparisons.len() - regressions - report.improvements().count(),
+        report.added.len(),
+        report.missing.len()
+    );
+    if report.comparisons.is_empty() {
+        return Err("nothing to compare (are both runs from the selected targets?)".to_string());
+    }
+    if regressions > 0 {
+        return Err(format!(
+            "{regressions} benchmark(s) regressed beyond their threshold"
+        ));
+    }
+    Ok(())
+}
+
+fn write_report(args: &[String]) -> Result<(), String> {
+    let mut names = Vec::new();
+    let mut snapshot = None;
+    let mut out = manifest_dir().join("target").join("report");
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--snapshot" => {
+                snapshot = Some(PathBuf::from(it.next().ok_or("--snapshot needs a value")?))
+            }
+            "--out" => out = PathBuf::from(it.next().ok_or("--out needs a value")?),
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{flag}`")),
+            name => names.push(name.to_string()),
+        }
+    }
+
+    let selected = select(&names)?;
+    let results = match snapshot {
+        Some(path) => read_snapshot(&path)?,
+        None => results::collect(&results::criterion_dir(manifest_dir()))?,
+    };
+    fs::create_dir_all(&out).map_err(|e| format!("cannot create {}: {e}", out.display()))?;
+
+    let mut index = String::from("# Findings\n\n");
+    let mut written = 0;
+    for experiment in report::EXPERIMENTS {
+        if !selected.iter().any(|b| b.name == experiment.target) {
+            continue;
+        }
+        let findings = experiment.findings(&results);
+        if findings.is_empty() {
+            eprintln!(
+                "skipping {}: no results for `{}`",
+                experiment.slug, experiment.target
+            );
+            continue;
+        }
+        let mut page = findings.to_markdown();
+        if vectorise::TARGETS.contains(&experiment.target) {
+            eprintln!("building {} to inspect vectorisation", experiment.target);
+            page.push_str(&vectorise::report(manifest_dir(), experiment.target)?);
+        }
+        let path = out.join(format!("{}.md", experiment.slug));
+        fs::write(&path, page).map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+        index.push_str(&format!(
+            "- [{}]({}.md) — `{}`\n",
+            experiment.question, experiment.slug, experiment.target
+        ));
+        written += 1;
+    }
+    if written == 0 {
+        return Err("no experiment had results (run the benches first)".to_string());
+    }
+
+    let path = out.join("index.md");
+    fs::write(&path, index).map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+    println!("wrote {written} experiment pages to {}", out.display());
+    Ok(())
+}
+
+fn inspect_asm(args: &[String]) -> Result<(), String> {
+    let mut target = None;
+    let mut wanted = Vec::new();
+    let mut format = Format::Asm;
+    let mut show = false;
+    let mut check = false;
+
+    for arg in args {
+        match arg.as_str() {
+            "--ir" => format = Format::LlvmIr,
+            "--show" => show = true,
+            "--check" => check = true,
+            flag if flag.starts_with("--") => return Err(format!("unknown option `{flag}`")),
+            name if target.is_none() => target = Some(name.to_string()),
+            name => wanted.push(name.to_string()),
+        }
+    }
+    let target = target.ok_or("asm needs a bench target")?;
+    let bench = suites::bench(&target)
+        .ok_or_else(|| format!("`{target}` is not a bench target (try `run-suite list`)"))?;
+    if check && format != Format::Asm {
+        return Err("--check works on assembly, drop --ir".to_string());
+    }
+
+    let path = asm::emit(manifest_dir(), bench.name, format)?;
+    let text =
+        fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    let functions = asm::parse(&text, format);
+
+    // Default to the functions the expectations talk about, or failing
+    // that, everything defined in the target itself.
+    if wanted.is_empty() {
+        wanted = asm::expectations(bench.name)
+            .map(|e| e.function.to_string())
+            .collect();
+    }
+    let selected: Vec<&asm::Function> = functions
+        .iter()
+        .filter(|f| {
+            if wanted.is_empty() {
+                f.name.starts_with(&format!("{}::", bench.name))
+            } else {
+                wanted.iter().any(|w| asm::matches(&f.name, bench.name, w))
+            }
+        })
+        .collect();
+
+    println!("{}", path.display());
+    if format == Format::Asm {
+        println!(
+            "{:<60} {:>8} {:>7} {:>10} {:>7} {:>7}",
+            "function", "instrs", "memcpy", "stack ops", "frame", "simd"
+        );
+    }
+    for f in &selected {
+        if format == Format::Asm {
+            let s = asm::stats(f);
+            println!(
+                "{:<60} {:>8} {:>7} {:>10} {:>7} {:>7}",
+                f.name, s.instructions, s.memcpy_calls, s.stack_ops, s.frame_bytes, s.packed_ops
+            );
+        } else {
+            println!("{}", f.name);
+        }
+        if show {
+            for line in &f.body {
+                println!("    {line}");
+            }
+            println!();
+        }
+    }
+
+    if !check {
+        return Ok(());
+    }
+    let source_path = manifest_dir()
+        .join("benches")
+        .join(format!("{}.rs", bench.name));
+    let source = fs::read_to_string(&source_path)
+        .map_err(|e| format!("cannot read {}: {e}", source_path.display()))?;
+    println!();
+    let mut failed = 0;
+    for e in asm::expectations(bench.name) {
+        match e.verify(&functions, &source) {
+            Ok(()) => println!("ok    {:<36} {}", e.function, e.claim),
+            Err(why) => {
+                failed += 1;
+                println!("FAIL  {:<36} {}\n      {why}", e.function, e.claim);
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{failed} codegen expectation(s) failed"));
+    }
+    Ok(())
+}
+
+fn compare_size(args: &[String]) -> Result<(), String> {
+    if let Some(flag) = args.iter().find(|a| a.starts_with("--")) {
+        return Err(format!("unknown option `{flag}`"));
+    }
+    if args.is_empty() {
+        return Err("size needs at least one suite or bench target".to_string());
+    }
+
+    for bench in select(args)? {
+        let mut rows: Vec<(&str, Footprint)> = Vec::new();
+        for strategy in size::STRATEGIES {
+            rows.push((
+                strategy.panic,
+                size::measure(manifest_dir(), bench.name, *strategy)?,
+            ));
+        }
+
+        println!("{}", bench.name);
+        println!(
+            "  {:<8} {:>12} {:>9} {:>10} {:>10} {:>13}",
+            "panic", "binary", "vs first", "functions", "instrs", "landing pads"
+        );
+        let base = rows[0].1.binary_bytes as f64;
+        for (panic, f) in &rows {
+            println!(
+                "  {:<8} {:>8} KiB {:>+8.1}% {:>10} {:>10} {:>13}",
+                panic,
+                f.binary_bytes / 1024,
+                (f.binary_bytes as f64 / base - 1.0) * 100.0,
+                f.functions,
+                f.instructions,
+                f.with_landing_pads
+            );
+        }
+    }
+    Ok(())
+}
+
+// -----------------------------
+// Helpers
+// -----------------------------
+
+fn select(names: &[String]) -> Result<Vec<&'static Bench>, String> {
+    suites::select(names).map_err(|name| {
+        format!("`{name}` is neither a suite nor a bench target (try `run-suite list`)")
+    })
+}
+
+/// Splits `args` at the first `--`; everything after it goes to Criterion.
+fn split_passthrough(args: &[String]) -> (&[String], &[String]) {
+    match args.iter().position(|a| a == "--") {
+        Some(i) => (&args[..i], &args[i + 1..]),
+        None => (args, &[]),
+    }
+}
+
+fn read_snapshot(path: &Path) -> Result<Vec<BenchResult>, String> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    let run = Run::from_json(&text).map_err(|e| format!("cannot parse {}: {e}", path.display()))?;
+    Ok(run.results)
+}
+
+/// Benchmark ids a target defines, from its `--list` output.
+fn list_ids(bench: &Bench) -> Result<Vec<String>, String> {
+    let mut cmd = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cmd.args(["bench", "-q", "--bench", bench.name, "--", "--list"]);
+    let out = cmd
+        .output()
+        .map_err(|e| format!("failed to start `{}`: {e}", render(&cmd)))?;
+    if !out.status.success() {
+        return Err(format!(
+            "`{}` exited with {}:\n{}",
+            render(&cmd),
+            out.status,
+            String::from_utf8_lossy(&out.stderr)
+        ));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|l| l.strip_suffix(": benchmark"))
+        .map(str::to_string)
+        .collect())
+}
+
+fn manifest_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn render(cmd: &Command) -> String {
+    let mut out = String::new();
+    for (key, value) in cmd.get_envs() {
+        if let Some(value) = value {
+            let value = value.to_string_lossy();
+            if value.contains(' ') {
+                out.push_str(&format!("{}=\"{value}\" ", key.to_string_lossy()));
+            } else {
+                out.push_str(&format!("{}={value} ", key.to_string_lossy()));
+            }
+        }
+    }
+    out.push_str(&cmd.get_program().to_string_lossy());
+    for arg in cmd.get_args() {
+        out.push(' ');
+        out.push_str(&arg.to_string_lossy());
+    }
+    out
+}
@@ -0,0 +1,250 @@
+//! Deterministic instruction-count mode, enabled with `--features cachegrind`.
+//!
+//! Bench targets end in [`bench_main!`](crate::bench_main) instead of
+//! `criterion_main!`. With the feature on, `cargo bench` no longer times
+//! anything: the bench binary lists its benchmark ids and re-runs itself once
+//! per id under `valgrind --tool=cachegrind`, using Criterion's `--test` mode
+//! so each routine executes exactly once. A run that matches no id is
+//! measured as well and subtracted, which removes process start-up and any
+//! setup a group does outside of `bench_function`.
+//!
+//! Estimated cycles use the usual weights: `L1 hits + 5 * LL hits + 35 * RAM
+//! accesses`. If valgrind is not installed, the binary prints a warning and
+//! falls back to a normal Criterion run.
+
+/// Totals from one cachegrind run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Costs {
+    /// Instructions executed.
+    pub instructions: u64,
+    /// Instruction fetches plus data reads and writes.
+    pub l1_accesses: u64,
+    /// L1 misses, which go to the last-level cache.
+    pub ll_accesses: u64,
+    /// Last-level misses, which go to memory.
+    pub ram_accesses: u64,
+}
+
+impl Costs {
+    pub fn estimated_cycles(&self) -> u64 {
+        let l1_hits = self.l1_accesses.saturating_sub(self.ll_accesses);
+        let ll_hits = self.ll_accesses.saturating_sub(self.ram_accesses);
+        l1_hits + 5 * ll_hits + 35 * self.ram_accesses
+    }
+
+    /// `self - baseline`, clamped at zero per counter.
+    pub fn minus(&self, baseline: &Costs) -> Costs {
+        Costs {
+            instructions: self.instructions.saturating_sub(baseline.instructions),
+            l1_accesses: self.l1_accesses.saturating_sub(baseline.l1_accesses),
+            ll_accesses: self.ll_accesses.saturating_sub(baseline.ll_accesses),
+            ram_accesses: self.ram_accesses.saturating_sub(baseline.ram_accesses),
+        }
+    }
+
+    /// Reads the `events:` and `summary:` lines of a `cachegrind.out` file.
+    pub fn parse(out: &str) -> Result<Costs, String> {
+        let line = |prefix: &str| {
+            out.lines()
+                .find_map(|l| l.strip_prefix(prefix))
+                .ok_or_else(|| format!("no `{prefix}` line in cachegrind output"))
+        };
+        let events: Vec<&str> = line("events:")?.split_whitespace().collect();
+        let values: Vec<u64> = line("summary:")?
+            .split_whitespace()
+            .map(|v| v.parse().map_err(|e| format!("bad count `{v}`: {e}")))
+            .collect::<Result<_, _>>()?;
+        let get = |name: &str| {
+            events
+                .iter()
+                .position(|e| *e == name)
+                .and_then(|i| values.get(i).copied())
+                .ok_or_else(|| format!("event `{name}` missing (was --cache-sim=yes passed?)"))
+        };
+
+        let (ir, dr, dw) = (get("Ir")?, get("Dr")?, get("Dw")?);
+        Ok(Costs {
+            instructions: ir,
+            l1_accesses: ir + dr + dw,
+            ll_accesses: get("I1mr")? + get("D1mr")? + get("D1mw")?,
+            ram_accesses: get("ILmr")? + get("DLmr")? + get("DLmw")?,
+        })
+    }
+}
+
+/// Called first thing by [`bench_main!`](crate::bench_main).
+///
+/// Returns `true` when the run was handled here and Criterion should not
+/// run; always `false` without the `cachegrind` feature.
+pub fn intercept() -> bool {
+    #[cfg(feature = "cachegrind")]
+    return runner::intercept();
+    #[cfg(not(feature = "cachegrind"))]
+    false
+}
+
+#[cfg(feature = "cachegrind")]
+mod runner {
+    use super::Costs;
+    use std::env;
+    use std::path::Path;
+    use std::process::Command;
+
+    /// Set in the environment of the re-executed bench binary.
+    const CHILD_ENV: &str = "RUST_EXAMPLES_CACHEGRIND_CHILD";
+
+    /// Id that matches no benchmark, used for the baseline run.
+    const NO_MATCH: &str = "cachegrind-baseline-matches-nothing";
+
+    pub fn intercept() -> bool {
+        let args: Vec<String> = env::args().skip(1).collect();
+        // Only take over real `cargo bench` runs, not `--list`/`--test` or
+        // our own child processes.
+        if env::var_os(CHILD_ENV).is_some() || !args.iter().any(|a| a == "--bench") {
+            return false;
+        }
+        if !valgrind_available() {
+            eprintln!("cachegrind: valgrind not found, falling back to a normal Criterion run");
+            return false;
+        }
+        if let Err(message) = run(&args) {
+            eprintln!("cachegrind: {message}");
+            std::process::exit(1);
+        }
+        true
+    }
+
+    fn valgrind_available() -> bool {
+        Command::new("valgrind")
+            .arg("--version")
+            .output()
+            .is_ok_and(|o| o.status.success())
+    }
+
+    fn run(args: &[String]) -> Result<(), String> {
+        let exe = env::current_exe().map_err(|e| format!("cannot locate bench binary: {e}"))?;
+
+        // Forward filters and the like to `--list` so the selection matches
+        // what a timed run would have covered.
+        let forwarded: Vec<&String> = args.iter().filter(|a| *a != "--bench").collect();
+        let listing = Command::new(&exe)
+            .args(&forwarded)
+            .arg("--list")
+            .env(CHILD_ENV, "1")
+            .output()
+            .map_err(|e| format!("failed to list benchmarks: {e}"))?;
+        let listing = String::from_utf8_lossy(&listing.stdout);
+        let ids: Vec<&str> = listing
+            .lines()
+            .filter_map(|l| l.strip_suffix(": benchmark"))
+            .collect();
+        if ids.is_empty() {
+            return Err("no benchmarks match the selection".to_string());
+        }
+
+        let baseline = measure(&exe, NO_MATCH)?;
+        println!(
+            "{:<56} {:>14} {:>14} {:>12} {:>10} {:>14}",
+            "benchmark", "instructions", "L1 accesses", "LL accesses", "RAM", "est. cycles"
+        );
+        for id in ids {
+            let costs = measure(&exe, id)?.minus(&baseline);
+            println!(
+                "{:<56} {:>14} {:>14} {:>12} {:>10} {:>14}",
+                id,
+                costs.instructions,
+                costs.l1_accesses,
+                costs.ll_accesses,
+                costs.ram_accesses,
+                costs.estimated_cycles()
+            );
+        }
+        Ok(())
+    }
+
+    /// Runs the benchmark `id` once under cachegrind and reads back its totals.
+    fn measure(exe: &Path, id: &str) -> Result<Costs, String> {
+        let out_file = env::temp_dir().join(format!("cachegrind.{}.out", std::process::id()));
+        let status = Command::new("valgrind")
+            .arg("--tool=cachegrind")
+            .arg("--cache-sim=yes")
+            .arg(format!("--cachegrind-out-file={}", out_file.display()))
+            .arg(exe)
+            .args(["--test", "--exact", id])
+            .env(CHILD_ENV, "1")
+            .output()
+            .map_err(|e| format!("failed to start valgrind: {e}"))?
+            .status;
+        if !status.success() {
+            return Err(format!("valgrind run of `{id}` exited with {status}"));
+        }
+        let out = std::fs::read_to_string(&out_file)
+            .map_err(|e| format!("cannot read {}: {e}", out_file.display()))?;
+        let _ = std::fs::remove_file(&out_file);
+        Costs::parse(&out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUT: &str = "\
+desc: I1 cache:         32768 B, 64 B, 8-way associative
+cmd: target/release/deps/array-0123 --bench --test
+events: Ir I1mr ILmr Dr D1mr DLmr Dw D1mw DLmw
+fl=main.rs
+fn=main
+1 100 1 1 20 2 1 10 1 0
+summary: 1000 10 5 200 20 4 100 8 2
+";
+
+    #[test]
+    fn parse_reads_the_summary_by_event_name() {
+        let costs = Costs::parse(OUT).unwrap();
+        assert_eq!(
+            costs,
+            Costs {
+                instructions: 1000,
+                l1_accesses: 1300,
+                ll_accesses: 38,
+                ram_accesses: 11,
+            }
+        );
+        assert_eq!(
+            costs.estimated_cycles(),
+            (1300 - 38) + 5 * (38 - 11) + 35 * 11
+        );
+    }
+
+    #[test]
+    fn parse_needs_the_cache_simulation() {
+        let out = "events: Ir\nsummary: 1000\n";
+        assert!(Costs::parse(out).unwrap_err().contains("cache-sim"));
+        assert!(Costs::parse("events: Ir\n").is_err());
+        assert!(Costs::parse("events: Ir\nsummary: x\n").is_err());
+    }
+
+    #[test]
+    fn minus_clamps_at_zero() {
+        let a = Costs {
+            instructions: 10,
+            l1_accesses: 5,
+            ll_accesses: 0,
+            ram_accesses: 3,
+        };
+        let b = Costs {
+            instructions: 4,
+            l1_accesses: 9,
+            ll_accesses: 1,
+            ram_accesses: 3,
+        };
+        assert_eq!(
+            a.minus(&b),
+            Costs {
+                instructions: 6,
+                ..Costs::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Compares two sets of [`BenchResult`]s and classifies every change.
+//!
+//! A change only counts as a regression (or improvement) when the mean moved
+//! by more than the threshold *and* the two confidence intervals do not
+//! overlap, so a single noisy sample cannot fail a pipeline on its own.
+
+use crate::perf;
+use crate::results::{BenchResult, Estimate};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Verdict::Regressed => "regressed",
+            Verdict::Improved => "improved",
+            Verdict::Unchanged => "unchanged",
+        })
+    }
+}
+
+/// One benchmark id present in both the baseline and the current run.
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub id: String,
+    pub baseline: Estimate,
+    pub current: Estimate,
+    /// Change of the mean, in percent of the baseline.
+    pub change: f64,
+    pub threshold: f64,
+    pub verdict: Verdict,
+}
+
+/// Result of [`compare`].
+#[derive(Debug, Default)]
+pub struct Report {
+    /// Ids found in both runs, in the current run's order.
+    pub comparisons: Vec<Comparison>,
+    /// Ids measured now but absent from the baseline.
+    pub added: Vec<String>,
+    /// Ids in the baseline that were not measured now.
+    pub missing: Vec<String>,
+}
+
+impl Report {
+    pub fn regressions(&self) -> impl Iterator<Item = &Comparison> {
+        self.with(Verdict::Regressed)
+    }
+
+    pub fn improvements(&self) -> impl Iterator<Item = &Comparison> {
+        self.with(Verdict::Improved)
+    }
+
+    fn with(&self, verdict: Verdict) -> impl Iterator<Item = &Comparison> {
+        self.comparisons
+            .iter()
+            .filter(move |c| c.verdict == verdict)
+    }
+}
+
+/// Compares mean estimates id by id.
+///
+/// `threshold` returns the tolerated change in percent for an id, or `None`
+/// to leave that id out entirely (e.g. because its target is not selected).
+pub fn compare(
+    baseline: &[BenchResult],
+    current: &[BenchResult],
+    threshold: impl Fn(&str) -> Option<f64>,
+) -> Report {
+    let mut report = Report::default();
+
+    for cur in current {
+        let Some(threshold) = threshold(&cur.id) else {
+            continue;
+        };
+        let Some(base) = baseline.iter().find(|b| b.id == cur.id) else {
+            report.added.push(cur.id.clone());
+            continue;
+        };
+        let change = (cur.mean.point - base.mean.point) / base.mean.point * 100.0;
+        let verdict = if change > threshold && cur.mean.lower > base.mean.upper {
+            Verdict::Regressed
+        } else if change < -threshold && cur.mean.upper < base.mean.lower {
+            Verdict::Improved
+        } else {
+            Verdict::Unchanged
+        };
+        report.comparisons.push(Comparison {
+            id: cur.id.clone(),
+            baseline: base.mean,
+            current: cur.mean,
+            change,
+            threshold,
+            verdict,
+        });
+    }
+
+    report.missing = baseline
+        .iter()
+        .filter(|b| threshold(&b.id).is_some())
+        .filter(|b| !current.iter().any(|c| c.id == b.id))
+        .map(|b| b.id.clone())
+        .collect();
+    report
+}
+
+/// Formats a wall-clock estimate given in nanoseconds with a readable unit.
+pub fn format_ns(ns: f64) -> String {
+    let (value, unit) = match ns.abs() {
+        v if v < 1e3 => (ns, "ns"),
+        v if v < 1e6 => (ns / 1e3, "µs"),
+        v if v < 1e9 => (ns / 1e6, "ms"),
+        _ => (ns / 1e9, "s"),
+    };
+    format!("{value:.2} {unit}")
+}
+
+/// Formats an estimate of `id` in its unit: a count for ids prefixed with a
+/// hardware counter, nanoseconds otherwise.
+pub fn format_value(id: &str, value: f64) -> String {
+    let Some(counter) = perf::counter_of(id) else {
+        return format_ns(value);
+    };
+    let (scaled, prefix) = match value.abs() {
+        v if v < 1e3 => (value, ""),
+        v if v < 1e6 => (value / 1e3, "K "),
+        v if v < 1e9 => (value / 1e6, "M "),
+        _ => (value / 1e9, "G "),
+    };
+    format!("{scaled:.2} {prefix}{counter}")
+}
+
+/// `mean [lower, upper]` for one estimate of `id`.
+pub fn format_interval(id: &str, e: &Estimate) -> String {
+    format!(
+        "{} [{}, {}]",
+        format_value(id, e.point),
+        format_value(id, e.lower),
+        format_value(id, e.upper)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(id: &str, point: f64, half_width: f64) -> BenchResult {
+        let e = Estimate {
+            point,
+            lower: point - half_width,
+            upper: point + half_width,
+            standard_error: 0.0,
+        };
+        BenchResult {
+            id: id.to_string(),
+            group: id.to_string(),
+            function: None,
+            parameter: None,
+            mean: e,
+            median: e,
+            std_dev: e,
+            slope: None,
+            throughput: None,
+            accuracy: None,
+        }
+    }
+
+    #[test]
+    fn verdicts_need_the_threshold_and_disjoint_intervals() {
+        let baseline = [
+            result("slower", 100.0, 1.0),
+            result("faster", 100.0, 1.0),
+            result("noisy", 100.0, 30.0),
+            result("small", 100.0, 1.0),
+            result("gone", 100.0, 1.0),
+        ];
+        let current = [
+            result("slower", 120.0, 1.0),
+            result("faster", 80.0, 1.0),
+            result("noisy", 120.0, 1.0),
+            result("small", 103.0, 1.0),
+            result("new", 100.0, 1.0),
+            result("skipped", 100.0, 1.0),
+        ];
+        let report = compare(&baseline, &current, |id| (id != "skipped").then_some(5.0));
+
+        let verdict = |id: &str| {
+            report
+                .comparisons
+                .iter()
+                .find(|c| c.id == id)
+                .map(|c| c.verdict)
+        };
+        assert_eq!(verdict("slower"), Some(Verdict::Regressed));
+        assert_eq!(verdict("faster"), Some(Verdict::Improved));
+        assert_eq!(verdict("noisy"), Some(Verdict::Unchanged));
+        assert_eq!(verdict("small"), Some(Verdict::Unchanged));
+        assert_eq!(report.added, ["new"]);
+        assert_eq!(report.missing, ["gone"]);
+        assert_eq!(report.regressions().count(), 1);
+        assert_eq!(report.improvements().count(), 1);
+    }
+
+    #[test]
+    fn values_are_formatted_in_their_unit() {
+        assert_eq!(format_ns(12.345), "12.35 ns");
+        assert_eq!(format_ns(1_500_000.0), "1.50 ms");
+        assert_eq!(format_value("sum/plain", 2_500.0), "2.50 µs");
+        assert_eq!(
+            format_value("instructions/sum/plain", 2_500.0),
+            "2.50 K instructions"
+        );
+        assert_eq!(format_value("instructions/x", 12.0), "12.00 instructions");
+        // Fallback clocks are nanoseconds even though they carry a label.
+        assert_eq!(format_value("task-clock/sum/plain", 2_500.0), "2.50 µs");
+    }
+}
@@ -0,0 +1,218 @@
+//! Deterministic input data for the benches.
+//!
+//! Every generator draws from a [`Gen`], a ChaCha8 stream with a recorded
+//! seed. Unlike `rand::rng()` (unseeded) or `StdRng` (whose algorithm may
+//! change between rand releases), ChaCha8 yields the same values on every
+//! machine, so two runs of a bench see the same inputs.
+//!
+//! The base seed is [`DEFAULT_SEED`] unless `BENCH_SEED` overrides it; it is
+//! recorded in exported results. Each [`Gen::new`] label derives its own
+//! stream from the base seed, so adding a generator to one bench does not
+//! shift the data another bench sees.
+
+use rand::distr::uniform::{SampleRange, SampleUniform};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::env;
+
+/// Environment variable overriding the base seed, decimal or `0x` hex.
+pub const SEED_ENV: &str = "BENCH_SEED";
+pub const DEFAULT_SEED: u64 = 0x5EED_0000_BE4C_0001;
+
+/// The base seed for this process.
+///
+/// Panics if `BENCH_SEED` is set but is not a number.
+pub fn base_seed() -> u64 {
+    let Ok(value) = env::var(SEED_ENV) else {
+        return DEFAULT_SEED;
+    };
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.unwrap_or_else(|_| panic!("{SEED_ENV}: `{value}` is not a u64"))
+}
+
+/// Mixes `label` into `seed` (FNV-1a, then a SplitMix64 finaliser).
+fn derive(seed: u64, label: &str) -> u64 {
+    let mut h = 0xcbf2_9ce4_8422_2325u64 ^ seed;
+    for b in label.bytes() {
+        h = (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3);
+    }
+    h = (h ^ (h >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
+
+/// How long [`Gen::strings`] makes each string.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lengths {
+    Fixed(usize),
+    /// Uniform in `min..=max`.
+    Uniform {
+        min: usize,
+        max: usize,
+    },
+    /// Normal-ish around `mean`, clamped at zero.
+    Normal {
+        mean: f64,
+        std_dev: f64,
+    },
+}
+
+/// Precomputed Zipf distribution over ranks `0..n`: rank `r` is drawn with
+/// probability proportional to `1 / (r + 1)^exponent`.
+#[derive(Clone, Debug)]
+pub struct Zipf {
+    cdf: Vec<f64>,
+}
+
+impl Zipf {
+    pub fn new(n: usize, exponent: f64) -> Zipf {
+        assert!(n > 0, "Zipf needs at least one rank");
+        let mut total = 0.0;
+        let cdf = (0..n)
+            .map(|r| {
+                total += 1.0 / ((r + 1) as f64).powf(exponent);
+                total
+            })
+            .collect();
+        Zipf { cdf }
+    }
+
+    pub fn sample(&self, source: &mut Gen) -> usize {
+        let u = source.rng.random::<f64>() * self.cdf[self.cdf.len() - 1];
+        self.cdf.partition_point(|&c| c < u).min(self.cdf.len() - 1)
+    }
+}
+
+/// A seeded, reproducible random stream.
+#[derive(Clone, Debug)]
+pub struct Gen {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl Gen {
+    /// The stream named `label` under the base seed.
+    pub fn new(label: &str) -> Gen {
+        Gen::with_seed(derive(base_seed(), label))
+    }
+
+    pub fn with_seed(seed: u64) -> Gen {
+        Gen {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// The seed this stream started from; [`Gen::with_seed`] replays it.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The underlying generator, for draws the helpers do not cover.
+    pub fn rng(&mut self) -> &mut ChaCha8Rng {
+        &mut self.rng
+    }
+
+    /// One value from `range`.
+    pub fn below<T: SampleUniform, R: SampleRange<T>>(&mut self, range: R) -> T {
+        self.rng.random_range(range)
+    }
+
+    /// `n` values drawn uniformly from `range`.
+    pub fn uniform<T, R>(&mut self, n: usize, range: R) -> Vec<T>
+    where
+        T: SampleUniform,
+        R: SampleRange<T> + Clone,
+    {
+        (0..n)
+            .map(|_| self.rng.random_range(range.clone()))
+            .collect()
+    }
+
+    /// `n` ranks in `0..ranks` with a Zipf skew; rank 0 is the hottest.
+    pub fn zipf(&mut self, n: usize, ranks: usize, exponent: f64) -> Vec<usize> {
+        let zipf = Zipf::new(ranks, exponent);
+        (0..n).map(|_| zipf.sample(self)).collect()
+    }
+
+    /// One normal-ish draw: the sum of twelve uniforms (Irwin-Hall), which
+    /// is close to normal and cheap, with tails cut at six deviations.
+    pub fn normal_one(&mut self, mean: f64, std_dev: f64) -> f64 {
+        let sum: f64 = (0..12).map(|_| self.rng.random::<f64>()).sum();
+        mean + (sum - 6.0) * std_dev
+    }
+
+    pub fn normal(&mut self, n: usize, mean: f64, std_dev: f64) -> Vec<f64> {
+        (0..n).map(|_| self.normal_one(mean, std_dev)).collect()
+    }
+
+    /// Uniform values, sorted ascending.
+    pub fn sorted<T, R>(&mut self, n: usize, range: R) -> Vec<T>
+    where
+        T: SampleUniform + Ord,
+        R: SampleRange<T> + Clone,
+    {
+        let mut v = self.uniform(n, range);
+        v.sort_unstable();
+        v
+    }
+
+    /// Uniform values, sorted descending.
+    pub fn reverse_sorted<T, R>(&mut self, n: usize, range: R) -> Vec<T>
+    where
+        T: SampleUniform + Ord,
+        R: SampleRange<T> + Clone,
+    {
+        let mut v = self.sorted(n, range);
+        v.reverse();
+        v
+    }
+
+    /// Sorted values with `percent`% of them swapped with a random partner.
+    pub fn nearly_sorted<T, R>(&mut self, n: usize, range: R, percent: usize) -> Vec<T>
+    where
+        T: SampleUniform + Ord,
+        R: SampleRange<T> + Clone,
+    {
+        let mut v = self.sorted(n, range);
+        if n > 1 {
+            for _ in 0..n * percent / 200 {
+                let (a, b) = (self.rng.random_range(0..n), self.rng.random_range(0..n));
+                v.swap(a, b);
+            }
+        }
+        v
+    }
+
+    /// `n` values with only `distinct` different ones, `0..distinct`.
+    pub fn duplicates(&mut self, n: usize, distinct: u64) -> Vec<u64> {
+        self.uniform(n, 0..distinct.max(1))
+    }
+
+    /// `n` lowercase alphanumeric strings with lengths drawn from `lengths`.
+    pub fn strings(&mut self, n: usize, lengths: Lengths) -> Vec<String> {
+        const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+        (0..n)
+            .map(|_| {
+                let len = match lengths {
+                    Lengths::Fixed(len) => len,
+                    Lengths::Uniform { min, max } => self.rng.random_range(min..=max),
+                    Lengths::Normal { mean, std_dev } => {
+                        self.normal_one(mean, std_dev).round().max(0.0) as usize
+                    }
+                };
+                (0..len)
+                    .map(|_| ALPHABET[self.rng.random_range(0..ALPHABET.len())] as char)
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        items.shuffle(&mut self.rng);
+    }
+}
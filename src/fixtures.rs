@@ -0,0 +1,183 @@
+//! Canonical payload shapes used by the ownership benches.
+//!
+//! `Small` (10 fields), `Medium` (20 fields) and `Large` (32 fields) mix
+//! plain scalars with heap-owning fields, and each has a `V2` twin with the
+//! same layout that the mapping benches convert into.
+//!
+//! Every type is built through [`Fixture::build`], which is deterministic:
+//! `size` is the length of every string, vector and map in the payload (map
+//! values have a fixed length), so `build(0)` allocates nothing and larger
+//! sizes scale the heap work linearly.
+//! [`Fixture::build_flat`] leaves the maps empty, for benches that only pass
+//! or move a payload around.
+
+use std::collections::HashMap;
+
+/// Payload size shared by the ownership benches, so their numbers compare.
+pub const BENCH_SIZE: usize = 2;
+
+/// Length of the strings and vectors stored as map values. Fixed, so the
+/// heap work of a payload grows linearly with its size.
+const MAP_VALUE_LEN: usize = 4;
+
+/// A payload type with a deterministic, size-parameterised builder.
+pub trait Fixture: Clone {
+    /// Number of fields in the struct.
+    const FIELDS: usize;
+
+    /// Builds a value whose strings and vectors hold `size` elements and
+    /// whose maps hold `map_len` entries.
+    fn build_with(size: usize, map_len: usize) -> Self;
+
+    /// Builds a value whose strings, vectors and maps each hold `size` elements.
+    fn build(size: usize) -> Self {
+        Self::build_with(size, size)
+    }
+
+    /// Like [`Fixture::build`], but with every map empty.
+    fn build_flat(size: usize) -> Self {
+        Self::build_with(size, 0)
+    }
+}
+
+// ---------------------------------------
+// Builders for individual fields
+// ---------------------------------------
+
+pub(crate) fn text(size: usize) -> String {
+    (0..size).map(|i| (b'a' + (i % 26) as u8) as char).collect()
+}
+
+fn seq<T: From<u8>>(size: usize) -> Vec<T> {
+    (0..size).map(|i| T::from((i % 251) as u8)).collect()
+}
+
+fn words(size: usize) -> Vec<String> {
+    (0..size).map(|i| text(i % 16 + 1)).collect()
+}
+
+fn map_of<K, V>(size: usize, key: impl Fn(usize) -> K, value: impl Fn(usize) -> V) -> HashMap<K, V>
+where
+    K: std::hash::Hash + Eq,
+{
+    (0..size).map(|i| (key(i), value(i))).collect()
+}
+
+fn key(i: usize) -> String {
+    format!("k{i:04}")
+}
+
+// Each shape and its `V2` twin come from one field list, so the two
+// cannot drift apart. `FIELDS` is counted from that list.
+macro_rules! fixtures {
+    ($($name:ident),+ = |$size:ident, $map_len:ident| $fields:tt) => {
+        $(fixtures!(@one $name, $size, $map_len, $fields);)+
+    };
+    (@one $name:ident, $size:ident, $map_len:ident, {
+        $($field:ident: $ty:ty = $init:expr),+ $(,)?
+    }) => {
+        #[derive(Clone, Debug)]
+        pub struct $name {
+            $(pub $field: $ty),+
+        }
+
+        impl Fixture for $name {
+            const FIELDS: usize = [$(stringify!($field)),+].len();
+
+            fn build_with($size: usize, $map_len: usize) -> Self {
+                $name {
+                    $($field: $init),+
+                }
+            }
+        }
+    };
+}
+
+// ---------------------------------------
+// Small (10 fields)
+// ---------------------------------------
+
+fixtures! {
+    Small, SmallV2 = |size, map_len| {
+        a: i32 = size as i32,
+        b: String = text(size),
+        c: f64 = size as f64 + 0.5,
+        d: Vec<u8> = seq(size),
+        e: HashMap<String, i32> = map_of(map_len, key, |i| i as i32),
+        f: bool = size.is_multiple_of(2),
+        g: Option<i32> = Some(42),
+        h: Vec<String> = words(size),
+        i: f32 = size as f32 + 0.25,
+        j: HashMap<String, Vec<i32>> = map_of(map_len, key, |_| seq(MAP_VALUE_LEN)),
+    }
+}
+
+// ---------------------------------------
+// Medium (20 fields)
+// ---------------------------------------
+
+fixtures! {
+    Medium, MediumV2 = |size, map_len| {
+        a: i32 = size as i32,
+        b: String = text(size),
+        c: f64 = size as f64 + 0.5,
+        d: Vec<u8> = seq(size),
+        e: HashMap<String, i32> = map_of(map_len, key, |i| i as i32),
+        f: i64 = size as i64 * 2,
+        g: Vec<i32> = seq(size),
+        h: String = text(size),
+        i: HashMap<i32, String> = map_of(map_len, |i| i as i32, |_| text(MAP_VALUE_LEN)),
+        j: f32 = size as f32 + 0.25,
+        k: Option<i32> = Some(42),
+        l: Vec<f64> = seq(size),
+        m: bool = size.is_multiple_of(2),
+        n: Vec<String> = words(size),
+        o: HashMap<i64, f64> = map_of(map_len, |i| i as i64, |i| i as f64),
+        p: String = text(size),
+        q: Option<Vec<i32>> = Some(seq(size)),
+        r: Vec<u32> = seq(size),
+        s: HashMap<String, Vec<u8>> = map_of(map_len, key, |_| seq(MAP_VALUE_LEN)),
+        t: f64 = size as f64 * 1.5,
+    }
+}
+
+// ---------------------------------------
+// Large (32 fields)
+// ---------------------------------------
+
+fixtures! {
+    Large, LargeV2 = |size, map_len| {
+        a: i64 = size as i64,
+        b: f32 = size as f32 + 0.25,
+        c: String = text(size),
+        d: Vec<u32> = seq(size),
+        e: HashMap<String, Vec<u8>> = map_of(map_len, key, |_| seq(MAP_VALUE_LEN)),
+        f: Option<u64> = Some(42),
+        g: i64 = size as i64 * 2,
+        h: Vec<i32> = seq(size),
+        i: HashMap<i32, String> = map_of(map_len, |i| i as i32, |_| text(MAP_VALUE_LEN)),
+        j: f64 = size as f64 + 0.5,
+        k: u32 = size as u32,
+        l: Vec<f64> = seq(size),
+        m: String = text(size),
+        n: HashMap<i64, i32> = map_of(map_len, |i| i as i64, |i| i as i32),
+        o: Vec<String> = words(size),
+        p: Vec<i64> = seq(size),
+        q: Vec<u8> = seq(size),
+        r: HashMap<String, String> = map_of(map_len, key, |_| text(MAP_VALUE_LEN)),
+        s: Vec<u32> = seq(size),
+        t: Option<Vec<i32>> = Some(seq(size)),
+        u: f64 = size as f64 * 1.5,
+        v: String = text(size),
+        w: i32 = size as i32 * 3,
+        x: f32 = size as f32 * 1.5,
+        y: i64 = size as i64 * 4,
+        z: String = text(size),
+        aa: Vec<f32> = seq(size),
+        ab: u64 = size as u64 * 5,
+        ac: HashMap<String, Vec<i32>> = map_of(map_len, key, |_| seq(MAP_VALUE_LEN)),
+        ad: Option<String> = Some(text(size)),
+        ae: Vec<String> = words(size),
+        af: HashMap<i64, f64> = map_of(map_len, |i| i as i64, |i| i as f64),
+    }
+}
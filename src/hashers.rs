@@ -0,0 +1,194 @@
+//! Hashers and key types for the map benches' hasher matrix.
+//!
+//! The map benches used to hash everything with the default SipHash
+//! `RandomState`. [`visit_all`] hands every (key type, hasher) pair to a
+//! [`Visitor`], so a bench can run the same workload over each combination
+//! and pick a hasher per workload from the numbers; [`visit_key`] narrows
+//! that to one key type, e.g. one benchmark group per key.
+
+use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+
+/// std's default: SipHash-1-3 with random keys, DoS resistant.
+pub type Sip = std::hash::RandomState;
+/// rustc's multiply-rotate hash: a few instructions per word, no seed.
+pub type Fx = BuildHasherDefault<rustc_hash::FxHasher>;
+/// Folded-multiply hash with random keys (AES rounds where available).
+pub type AHash = ahash::RandomState;
+/// Integer keys hash to themselves.
+pub type Identity = BuildHasherDefault<IdentityHasher>;
+
+/// Passes an integer key through unchanged.
+///
+/// Only meaningful for keys that hash as a single integer; anything that
+/// writes bytes panics rather than silently colliding. `HashMap` takes its
+/// 7-bit control tags from the top of the hash, which is zero for small
+/// integers, so large maps degrade towards linear probing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IdentityHasher(u64);
+
+impl Hasher for IdentityHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, _bytes: &[u8]) {
+        panic!("IdentityHasher only hashes integer keys");
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.0 = n.into();
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.0 = n.into();
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.0 = n.into();
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = n;
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.0 = n as u64;
+    }
+}
+
+// -----------------------------
+// Keys
+// -----------------------------
+
+/// A key type in the matrix.
+pub trait Key: Hash + Eq + Ord + Clone + 'static {
+    const NAME: &'static str;
+    /// Whether the key hashes as one integer, so [`Identity`] applies.
+    const INTEGER: bool;
+
+    /// The `i`-th key; distinct `i` give distinct keys.
+    fn nth(i: u64) -> Self;
+}
+
+impl Key for u32 {
+    const NAME: &'static str = "u32";
+    const INTEGER: bool = true;
+
+    fn nth(i: u64) -> u32 {
+        i as u32
+    }
+}
+
+impl Key for u64 {
+    const NAME: &'static str = "u64";
+    const INTEGER: bool = true;
+
+    fn nth(i: u64) -> u64 {
+        i
+    }
+}
+
+/// Eight bytes, like a short code or username.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShortString(pub String);
+
+impl Key for ShortString {
+    const NAME: &'static str = "short_string";
+    const INTEGER: bool = false;
+
+    fn nth(i: u64) -> ShortString {
+        ShortString(format!("k{i:07}"))
+    }
+}
+
+/// Sixty-four bytes sharing a long prefix, like a URL or file path.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LongString(pub String);
+
+impl Key for LongString {
+    const NAME: &'static str = "long_string";
+    const INTEGER: bool = false;
+
+    fn nth(i: u64) -> LongString {
+        LongString(format!("https://api.example.com/v1/tenants/users/{i:023}"))
+    }
+}
+
+/// A multi-field key, hashed field by field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompositeKey {
+    pub tenant: u32,
+    pub user: u64,
+    pub region: u16,
+}
+
+impl Key for CompositeKey {
+    const NAME: &'static str = "composite";
+    const INTEGER: bool = false;
+
+    fn nth(i: u64) -> CompositeKey {
+        CompositeKey {
+            tenant: (i % 64) as u32,
+            user: i,
+            region: (i % 7) as u16,
+        }
+    }
+}
+
+/// Key type names in visiting order.
+pub const KEYS: &[&str] = &[
+    u32::NAME,
+    u64::NAME,
+    ShortString::NAME,
+    LongString::NAME,
+    CompositeKey::NAME,
+];
+
+// -----------------------------
+// Matrix
+// -----------------------------
+
+/// Name of [`Sip`], the hasher the map benches used before the matrix.
+/// Ids measured with it keep their old names so saved baselines still match.
+pub const DEFAULT: &str = "sip";
+
+/// Receives each (key, hasher) pair in turn; see [`visit_all`].
+pub trait Visitor {
+    fn visit<K: Key, S: BuildHasher + Default + Clone>(&mut self, hasher: &'static str);
+}
+
+/// Calls `visitor` for every key type with every hasher that applies to it.
+pub fn visit_all<V: Visitor>(visitor: &mut V) {
+    visit_hashers::<u32, V>(visitor);
+    visit_hashers::<u64, V>(visitor);
+    visit_hashers::<ShortString, V>(visitor);
+    visit_hashers::<LongString, V>(visitor);
+    visit_hashers::<CompositeKey, V>(visitor);
+}
+
+/// Like [`visit_all`], for the key type called `key` (one of [`KEYS`]) only.
+pub fn visit_key<V: Visitor>(key: &str, visitor: &mut V) {
+    struct Only<'a, V> {
+        key: &'a str,
+        visitor: &'a mut V,
+    }
+
+    impl<V: Visitor> Visitor for Only<'_, V> {
+        fn visit<K: Key, S: BuildHasher + Default + Clone>(&mut self, hasher: &'static str) {
+            if K::NAME == self.key {
+                self.visitor.visit::<K, S>(hasher);
+            }
+        }
+    }
+
+    visit_all(&mut Only { key, visitor });
+}
+
+fn visit_hashers<K: Key, V: Visitor>(visitor: &mut V) {
+    visitor.visit::<K, Sip>(DEFAULT);
+    visitor.visit::<K, Fx>("fx");
+    visitor.visit::<K, AHash>("ahash");
+    if K::INTEGER {
+        visitor.visit::<K, Identity>("identity");
+    }
+}
@@ -0,0 +1,50 @@
+//! Shared building blocks for the benchmarks in `benches/`.
+//!
+//! Bench targets are compiled as separate crates, so anything two of them
+//! need to agree on (payload shapes, helpers) lives here instead of being
+//! copy-pasted into each file.
+
+pub mod alloc;
+pub mod asm;
+pub mod cachegrind;
+pub mod compare;
+pub mod data;
+pub mod executor;
+pub mod fixtures;
+pub mod hashers;
+pub mod perf;
+pub mod report;
+pub mod results;
+pub mod service;
+pub mod size;
+pub mod suites;
+pub mod sweep;
+pub mod vectorise;
+
+#[doc(hidden)]
+pub use criterion as __criterion;
+
+/// Drop-in replacement for `criterion_main!` used by every bench target.
+///
+/// Behaves exactly like `criterion_main!` unless the `cachegrind` or
+/// `count-allocs` feature is on, in which case [`cachegrind::intercept`] or
+/// [`alloc::intercept`] gets a chance to take over the run first.
+#[macro_export]
+macro_rules! bench_main {
+    ( $( $group:path ),+ $(,)* ) => {
+        fn main() {
+            if $crate::cachegrind::intercept() || $crate::alloc::intercept() {
+                return;
+            }
+            $crate::results::record_build(env!("CARGO_CRATE_NAME"));
+
+            $(
+                $group();
+            )+
+
+            $crate::__criterion::Criterion::default()
+                .configure_from_args()
+                .final_summary();
+        }
+    };
+}
@@ -0,0 +1,508 @@
+//! A Criterion [`Measurement`] backed by CPU performance counters.
+//!
+//! On Linux the counter is opened with `perf_event_open` for the calling
+//! thread, user space only. When hardware counters are unavailable (VMs,
+//! containers without a PMU) it falls back to the software task-clock, and
+//! if `perf_event_open` is blocked entirely, to the thread CPU-time clock.
+//! Off unix there is no thread clock to fall back to, so it uses wall time.
+//! [`PerfCounter::label`] says which one ended up being used.
+//!
+//! When more events are open than the PMU has counters (under `perf stat`,
+//! say), the kernel multiplexes them and each one only counts part of the
+//! time. Counts are then scaled up by the time the counter was enabled over
+//! the time it ran, as `perf stat` does, with a warning that they are
+//! estimates.
+
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::{Criterion, Throughput};
+use std::env;
+use std::fmt;
+use std::sync::Once;
+
+/// Environment variable naming the counter [`PerfCounter::from_env`] opens.
+pub const COUNTER_ENV: &str = "BENCH_PERF_COUNTER";
+
+/// A hardware event to count.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Counter {
+    Cycles,
+    Instructions,
+    BranchMisses,
+    CacheMisses,
+}
+
+impl Counter {
+    pub const ALL: [Counter; 4] = [
+        Counter::Cycles,
+        Counter::Instructions,
+        Counter::BranchMisses,
+        Counter::CacheMisses,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Counter::Cycles => "cycles",
+            Counter::Instructions => "instructions",
+            Counter::BranchMisses => "branch-misses",
+            Counter::CacheMisses => "cache-misses",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Counter> {
+        Counter::ALL.into_iter().find(|c| c.name() == name)
+    }
+
+    fn units(self) -> &'static Units {
+        match self {
+            Counter::Cycles => &CYCLES,
+            Counter::Instructions => &INSTRUCTIONS,
+            Counter::BranchMisses => &BRANCH_MISSES,
+            Counter::CacheMisses => &CACHE_MISSES,
+        }
+    }
+}
+
+/// The hardware counter a benchmark id was counted in, from the label
+/// [`run_counted`] prefixes it with; `None` for ids measured in nanoseconds
+/// (wall time and the clock fallbacks).
+pub fn counter_of(id: &str) -> Option<Counter> {
+    Counter::ALL.into_iter().find(|c| {
+        id.strip_prefix(c.name())
+            .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+impl fmt::Display for Counter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Where the measured values actually come from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The requested hardware counter.
+    Hardware(Counter),
+    /// Software task-clock, in nanoseconds.
+    TaskClock,
+    /// `CLOCK_THREAD_CPUTIME_ID`, in nanoseconds.
+    ThreadCpuTime,
+    /// Monotonic wall time, in nanoseconds; only used off unix.
+    WallTime,
+}
+
+/// One read of a counter: its running total, and how long it has been
+/// enabled and actually counting, in nanoseconds. The clock fallbacks
+/// always count, so their times stay zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Reading {
+    value: u64,
+    enabled: u64,
+    running: u64,
+}
+
+/// Counts one event for the current thread; see the module docs for fallbacks.
+pub struct PerfCounter {
+    source: Source,
+    #[cfg(target_os = "linux")]
+    fd: Option<std::os::fd::OwnedFd>,
+}
+
+impl PerfCounter {
+    pub fn new(counter: Counter) -> PerfCounter {
+        #[cfg(target_os = "linux")]
+        {
+            if let Some(fd) = sys::open(sys::PERF_TYPE_HARDWARE, sys::hardware_config(counter)) {
+                return PerfCounter {
+                    source: Source::Hardware(counter),
+                    fd: Some(fd),
+                };
+            }
+            if let Some(fd) = sys::open(sys::PERF_TYPE_SOFTWARE, sys::PERF_COUNT_SW_TASK_CLOCK) {
+                return PerfCounter {
+                    source: Source::TaskClock,
+                    fd: Some(fd),
+                };
+            }
+            PerfCounter {
+                source: Source::ThreadCpuTime,
+                fd: None,
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = counter;
+            PerfCounter {
+                source: if cfg!(unix) {
+                    Source::ThreadCpuTime
+                } else {
+                    Source::WallTime
+                },
+            }
+        }
+    }
+
+    /// Opens the counter named by `BENCH_PERF_COUNTER`, defaulting to instructions.
+    ///
+    /// Panics on an unknown name so a typo does not silently measure
+    /// something else.
+    pub fn from_env() -> PerfCounter {
+        let counter = match env::var(COUNTER_ENV) {
+            Ok(name) => Counter::from_name(&name)
+                .unwrap_or_else(|| panic!("{COUNTER_ENV}={name} is not a known counter")),
+            Err(_) => Counter::Instructions,
+        };
+        PerfCounter::new(counter)
+    }
+
+    pub fn source(&self) -> Source {
+        self.source
+    }
+
+    /// Short name of the source, suitable as a benchmark-id prefix.
+    pub fn label(&self) -> &'static str {
+        match self.source {
+            Source::Hardware(counter) => counter.name(),
+            Source::TaskClock => "task-clock",
+            Source::ThreadCpuTime => "thread-cputime",
+            Source::WallTime => "wall-time",
+        }
+    }
+
+    fn read(&self) -> Reading {
+        #[cfg(target_os = "linux")]
+        if let Some(fd) = &self.fd {
+            return sys::read(fd);
+        }
+        Reading {
+            value: thread_cpu_time_ns(),
+            ..Reading::default()
+        }
+    }
+
+    /// The count between `start` and `end`, scaled up for the part of the
+    /// interval the kernel had the counter multiplexed out.
+    ///
+    /// Panics if the counter never ran in the interval, since there is
+    /// nothing to scale then.
+    fn count(&self, start: Reading, end: Reading) -> u64 {
+        let value = end.value.saturating_sub(start.value);
+        let enabled = end.enabled - start.enabled;
+        let running = end.running - start.running;
+        if running == enabled {
+            return value;
+        }
+        assert!(
+            running > 0,
+            "{} never got a hardware counter during a sample; \
+             close other perf events (e.g. `perf stat`) and rerun",
+            self.label()
+        );
+        static MULTIPLEXED: Once = Once::new();
+        MULTIPLEXED.call_once(|| {
+            eprintln!(
+                "warning: {} is multiplexed with other perf events; \
+                 counts are scaled by enabled/running time and are estimates",
+                self.label()
+            )
+        });
+        (value as u128 * enabled as u128 / running as u128) as u64
+    }
+
+    fn units(&self) -> &'static Units {
+        match self.source {
+            Source::Hardware(counter) => counter.units(),
+            Source::TaskClock | Source::ThreadCpuTime | Source::WallTime => &NANOS,
+        }
+    }
+}
+
+impl Measurement for PerfCounter {
+    type Intermediate = Reading;
+    type Value = u64;
+
+    fn start(&self) -> Reading {
+        self.read()
+    }
+
+    fn end(&self, start: Reading) -> u64 {
+        self.count(start, self.read())
+    }
+
+    fn add(&self, v1: &u64, v2: &u64) -> u64 {
+        v1 + v2
+    }
+
+    fn zero(&self) -> u64 {
+        0
+    }
+
+    fn to_f64(&self, value: &u64) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        self.units()
+    }
+}
+
+/// Runs `targets` once more under a [`PerfCounter::from_env`] measurement.
+///
+/// `targets` receives the counter's label to prefix its benchmark ids with,
+/// so counted results do not overwrite the wall-clock ones under
+/// `target/criterion`. Call it from a plain function listed in `criterion_main!`.
+pub fn run_counted(targets: impl FnOnce(&mut Criterion<PerfCounter>, &str)) {
+    let counter = PerfCounter::from_env();
+    let label = counter.label();
+    let mut criterion = Criterion::default()
+        .with_measurement(counter)
+        .configure_from_args();
+    targets(&mut criterion, label);
+}
+
+#[cfg(unix)]
+fn thread_cpu_time_ns() -> u64 {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: `ts` is a valid, writable timespec.
+    unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut ts) };
+    ts.tv_sec as u64 * 1_000_000_000 + ts.tv_nsec as u64
+}
+
+// No thread CPU clock here: nanoseconds since the first read instead.
+#[cfg(not(unix))]
+fn thread_cpu_time_ns() -> u64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    EPOCH.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
+
+// -----------------------------
+// Formatting
+// -----------------------------
+
+/// Unit strings for one kind of value, from smallest to largest scale.
+struct Units {
+    scaled: [&'static str; 4],
+    per_element: &'static str,
+    per_byte: &'static str,
+}
+
+static CYCLES: Units = Units {
+    scaled: ["cycles", "Kcycles", "Mcycles", "Gcycles"],
+    per_element: "elem/cycle",
+    per_byte: "B/cycle",
+};
+static INSTRUCTIONS: Units = Units {
+    scaled: ["instr", "Kinstr", "Minstr", "Ginstr"],
+    per_element: "elem/instr",
+    per_byte: "B/instr",
+};
+static BRANCH_MISSES: Units = Units {
+    scaled: ["br-miss", "Kbr-miss", "Mbr-miss", "Gbr-miss"],
+    per_element: "elem/br-miss",
+    per_byte: "B/br-miss",
+};
+static CACHE_MISSES: Units = Units {
+    scaled: ["c-miss", "Kc-miss", "Mc-miss", "Gc-miss"],
+    per_element: "elem/c-miss",
+    per_byte: "B/c-miss",
+};
+static NANOS: Units = Units {
+    scaled: ["ns", "µs", "ms", "s"],
+    per_element: "elem/ns",
+    per_byte: "B/ns",
+};
+
+impl ValueFormatter for Units {
+    fn scale_values(&self, typical_value: f64, values: &mut [f64]) -> &'static str {
+        let (factor, unit) = match typical_value {
+            v if v < 1e3 => (1.0, self.scaled[0]),
+            v if v < 1e6 => (1e3, self.scaled[1]),
+            v if v < 1e9 => (1e6, self.scaled[2]),
+            _ => (1e9, self.scaled[3]),
+        };
+        for v in values {
+            *v /= factor;
+        }
+        unit
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        let (per, unit) = match *throughput {
+            Throughput::Bytes(n) | Throughput::BytesDecimal(n) => (n as f64, self.per_byte),
+            Throughput::Elements(n) => (n as f64, self.per_element),
+        };
+        for v in values {
+            *v = per / *v;
+        }
+        unit
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        self.scaled[0]
+    }
+}
+
+// -----------------------------
+// perf_event_open(2)
+// -----------------------------
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use super::{Counter, Reading};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+    pub const PERF_TYPE_HARDWARE: u32 = 0;
+    pub const PERF_TYPE_SOFTWARE: u32 = 1;
+    pub const PERF_COUNT_SW_TASK_CLOCK: u64 = 1;
+
+    const PERF_COUNT_HW_CPU_CYCLES: u64 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+    const PERF_FORMAT_TOTAL_TIME_ENABLED: u64 = 1 << 0;
+    const PERF_FORMAT_TOTAL_TIME_RUNNING: u64 = 1 << 1;
+    const EXCLUDE_KERNEL: u64 = 1 << 5;
+    const EXCLUDE_HV: u64 = 1 << 6;
+
+    /// The leading `PERF_ATTR_SIZE_VER1` bytes of `struct perf_event_attr`.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+        config2: u64,
+    }
+
+    pub fn hardware_config(counter: Counter) -> u64 {
+        match counter {
+            Counter::Cycles => PERF_COUNT_HW_CPU_CYCLES,
+            Counter::Instructions => PERF_COUNT_HW_INSTRUCTIONS,
+            Counter::BranchMisses => PERF_COUNT_HW_BRANCH_MISSES,
+            Counter::CacheMisses => PERF_COUNT_HW_CACHE_MISSES,
+        }
+    }
+
+    /// Opens an enabled, user-space-only counter for the calling thread.
+    pub fn open(kind: u32, config: u64) -> Option<OwnedFd> {
+        let attr = PerfEventAttr {
+            kind,
+            size: size_of::<PerfEventAttr>() as u32,
+            config,
+            read_format: PERF_FORMAT_TOTAL_TIME_ENABLED | PERF_FORMAT_TOTAL_TIME_RUNNING,
+            flags: EXCLUDE_KERNEL | EXCLUDE_HV,
+            ..PerfEventAttr::default()
+        };
+        // SAFETY: `attr` outlives the call and its `size` matches the struct.
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_perf_event_open,
+                &attr as *const PerfEventAttr,
+                0,  // this thread
+                -1, // any CPU
+                -1, // no group
+                PERF_FLAG_FD_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return None;
+        }
+        // SAFETY: the kernel just handed us this descriptor.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd as i32) };
+        // Some VMs accept the event but never schedule it on a counter;
+        // treat that as unavailable. The count itself may legitimately stay
+        // at zero (no branch or cache misses in a short loop), so only the
+        // time it spent running is checked.
+        for i in 0..1_000u64 {
+            std::hint::black_box(i);
+        }
+        (read(&fd).running > 0).then_some(fd)
+    }
+
+    /// What `read(2)` returns for the `read_format` [`open`] asks for.
+    #[repr(C)]
+    #[derive(Default)]
+    struct ReadFormat {
+        value: u64,
+        time_enabled: u64,
+        time_running: u64,
+    }
+
+    pub fn read(fd: &OwnedFd) -> Reading {
+        let mut out = ReadFormat::default();
+        // SAFETY: reading at most `size_of::<ReadFormat>()` bytes into it.
+        let n = unsafe {
+            libc::read(
+                fd.as_raw_fd(),
+                (&mut out as *mut ReadFormat).cast(),
+                size_of::<ReadFormat>(),
+            )
+        };
+        assert_eq!(
+            n,
+            size_of::<ReadFormat>() as isize,
+            "short read from perf counter"
+        );
+        Reading {
+            value: out.value,
+            enabled: out.time_enabled,
+            running: out.time_running,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counter() -> PerfCounter {
+        PerfCounter {
+            source: Source::TaskClock,
+            #[cfg(target_os = "linux")]
+            fd: None,
+        }
+    }
+
+    fn reading(value: u64, enabled: u64, running: u64) -> Reading {
+        Reading {
+            value,
+            enabled,
+            running,
+        }
+    }
+
+    #[test]
+    fn counts_are_scaled_by_the_time_multiplexed_out() {
+        let c = counter();
+        let start = reading(100, 1_000, 1_000);
+        assert_eq!(c.count(start, reading(600, 2_000, 2_000)), 500);
+        assert_eq!(c.count(start, reading(600, 3_000, 2_000)), 1_000);
+        assert_eq!(c.count(Reading::default(), reading(7, 0, 0)), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "never got a hardware counter")]
+    fn a_counter_that_never_ran_is_an_error() {
+        counter().count(reading(0, 0, 0), reading(0, 1_000, 0));
+    }
+}
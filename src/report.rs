@@ -0,0 +1,877 @@
+//! Turns collected results into one markdown findings page per experiment.
+//!
+//! An [`Experiment`] names the question a bench file asks and describes its
+//! benchmark ids with a pattern: `{variant}` stands for the thing being
+//! compared, `{case}` for the size or shape it is compared at, and `*` for
+//! anything else that varies (e.g. a trailing parameter). Every variant is
+//! reported as a speedup over the declared baseline variant of the same case.
+
+use crate::compare::format_ns;
+use crate::results::{BenchResult, Estimate};
+use std::fmt::Write;
+
+/// One question answered by a group of related benchmark ids.
+#[derive(Debug)]
+pub struct Experiment {
+    /// File name of the generated page, without extension.
+    pub slug: &'static str,
+    /// Bench target the ids come from.
+    pub target: &'static str,
+    pub question: &'static str,
+    pub pattern: &'static str,
+    pub variants: &'static [&'static str],
+    /// Variant the others are measured against; must be in `variants`.
+    pub baseline: &'static str,
+}
+
+pub const EXPERIMENTS: &[Experiment] = &[
+    // containers
+    Experiment {
+        slug: "vec-with-capacity",
+        target: "vector_allocations",
+        question: "Does pre-sizing a `Vec` beat letting it grow?",
+        pattern: "vec_make_and_fill_N={case}/{variant}",
+        variants: &[
+            "Vec::new() then push N (growing)",
+            "Vec::with_capacity(N) then push N",
+        ],
+        baseline: "Vec::new() then push N (growing)",
+    },
+    Experiment {
+        slug: "vec-smallvec-tinyvec-growth",
+        target: "vectors_allocations",
+        question: "Do `SmallVec`/`TinyVec` beat `Vec`, pre-sized or growing?",
+        pattern: "make_and_fill_all_N={case}/{variant}",
+        variants: &[
+            "Vec::with_capacity(N) + push N",
+            "Vec::new() + push N (growing)",
+            "SmallVec::with_capacity(N) + push N",
+            "SmallVec::new() + push N (growing/inline→spill)",
+            "TinyVec::with_capacity(N) + push N",
+            "TinyVec::new() + push N (growing/inline→spill)",
+        ],
+        baseline: "Vec::with_capacity(N) + push N",
+    },
+    Experiment {
+        slug: "vec-smallvec-tinyvec-prealloc",
+        target: "vectors_fight",
+        question: "Pre-sized, do `SmallVec`/`TinyVec` beat `Vec`?",
+        pattern: "make_and_fill_prealloc_N={case}/{variant}",
+        variants: &[
+            "Vec::with_capacity(N) + push N",
+            "SmallVec::with_capacity(N) + push N",
+            "TinyVec::with_capacity(N) + push N",
+        ],
+        baseline: "Vec::with_capacity(N) + push N",
+    },
+    Experiment {
+        slug: "many-tiny-vecs",
+        target: "vectors_fight_2",
+        question: "Does `SmallVec` beat `Vec` for many tiny vectors (K=8)?",
+        pattern: "{case}/{variant}",
+        variants: &[
+            "Vec (alloc per tiny vec)",
+            "Vec reserve K (alloc per tiny vec)",
+            "SmallVec<[K]> (inline, noalloc)",
+            "TinyVec<[K]> (inline, noalloc)",
+        ],
+        baseline: "Vec (alloc per tiny vec)",
+    },
+    Experiment {
+        slug: "buffer-reuse",
+        target: "buffer_reuse",
+        question: "Where should a hot loop get its tiny buffers from?",
+        pattern: "buffer_reuse_{case}/{variant}",
+        variants: &[
+            "fresh_vec",
+            "clear_reuse",
+            "truncate_reuse",
+            "drain_reuse",
+            "pool",
+            "thread_local",
+            "bump_arena",
+        ],
+        baseline: "fresh_vec",
+    },
+    Experiment {
+        slug: "small-maps",
+        target: "maps_fight",
+        question: "Do `SmallMap`/`ArrayMap` beat `HashMap` for small maps?",
+        pattern: "map_make_and_fill_small_N={case}/{variant}",
+        variants: &[
+            "HashMap::with_capacity(N) + insert N",
+            "HashMap<fx>::with_capacity(N) + insert N",
+            "HashMap<ahash>::with_capacity(N) + insert N",
+            "HashMap<identity>::with_capacity(N) + insert N",
+            "SmallMap::new() + insert N",
+            "ArrayMap::new() + insert N (stack, fixed)",
+        ],
+        baseline: "HashMap::with_capacity(N) + insert N",
+    },
+    Experiment {
+        slug: "large-maps",
+        target: "maps_fight",
+        question: "Does `SmallMap` still keep up with `HashMap` once it spills?",
+        pattern: "map_make_and_fill_large_N={case}/{variant}",
+        variants: &[
+            "HashMap::with_capacity(N) + insert N",
+            "HashMap<fx>::with_capacity(N) + insert N",
+            "HashMap<ahash>::with_capacity(N) + insert N",
+            "HashMap<identity>::with_capacity(N) + insert N",
+            "SmallMap::new() + insert N (may spill)",
+        ],
+        baseline: "HashMap::with_capacity(N) + insert N",
+    },
+    Experiment {
+        slug: "hasher-fill",
+        target: "maps_fight",
+        question: "Which hasher fills a 100k-entry `HashMap` fastest for each key type?",
+        pattern: "map_hasher_fill_{case}/{variant}/100000",
+        variants: &["sip", "fx", "ahash", "identity"],
+        baseline: "sip",
+    },
+    Experiment {
+        slug: "map-workloads-small",
+        target: "map_workloads",
+        question: "Which map suits each access pattern at 64 entries?",
+        pattern: "workload_{case}/{variant}/64",
+        variants: &["hashmap", "btreemap", "smallmap", "arraymap", "sorted_vec"],
+        baseline: "hashmap",
+    },
+    Experiment {
+        slug: "map-workloads-large",
+        target: "map_workloads",
+        question: "Which map suits each access pattern at 10k entries?",
+        pattern: "workload_{case}/{variant}/10000",
+        variants: &["hashmap", "btreemap", "smallmap", "sorted_vec"],
+        baseline: "hashmap",
+    },
+    Experiment {
+        slug: "map-vs-sorted-vec",
+        target: "vector_map",
+        question: "Is a sorted `Vec` with binary search faster than a map for lookups?",
+        pattern: "{variant}_{case} elements",
+        variants: &[
+            "map_search",
+            "map_search_fx",
+            "map_search_ahash",
+            "map_search_identity",
+            "btree_map_search",
+            "vec_binary_search_presorted",
+            "vec_sort_and_binary_search",
+        ],
+        baseline: "map_search",
+    },
+    Experiment {
+        slug: "hasher-lookup",
+        target: "vector_map",
+        question: "Which hasher looks up keys fastest in a 100k-entry `HashMap` for each key type?",
+        pattern: "map_hasher_lookup_{case}/{variant}/100000",
+        variants: &["sip", "fx", "ahash", "identity"],
+        baseline: "sip",
+    },
+    // ownership
+    Experiment {
+        slug: "array-return",
+        target: "array",
+        question: "Does returning an array by value cost a memcpy compared to an out-parameter?",
+        pattern: "return_overhead_{case}/{variant}/*",
+        variants: &[
+            "manual_memcpy_noinline",
+            "id_return_noinline",
+            "build_return_inline",
+            "build_outparam_inline",
+        ],
+        baseline: "build_outparam_inline",
+    },
+    Experiment {
+        slug: "return-clone-move-ref",
+        target: "clone_move_reference",
+        question: "What does returning a clone or a reference cost compared to a move?",
+        pattern: "return_{case}_{variant}",
+        variants: &["move", "clone", "ref", "mut_ref"],
+        baseline: "move",
+    },
+    Experiment {
+        slug: "construct-direct-move-boxed",
+        target: "constructor",
+        question: "Is constructing into a `Box` slower than building by move?",
+        pattern: "construct_{case}_{variant}",
+        variants: &["direct", "move", "boxed"],
+        baseline: "move",
+    },
+    Experiment {
+        slug: "integer-move-vs-ref",
+        target: "integer",
+        question: "Is passing `i64`/`i128` by reference slower than by value?",
+        pattern: "{case}::{variant}",
+        variants: &[
+            "takes_by_move",
+            "takes_by_ref",
+            "takes_and_returns_by_ref",
+            "takes_by_move_without_return",
+            "takes_without_returning_by_ref",
+        ],
+        baseline: "takes_by_move",
+    },
+    Experiment {
+        slug: "integer-overflow-handling",
+        target: "integer",
+        question: "What do checked, wrapping, saturating and overflowing arithmetic cost next to plain operators?",
+        pattern: "int_{case}/{variant}",
+        variants: &["plain", "checked", "wrapping", "saturating", "overflowing"],
+        baseline: "plain",
+    },
+    Experiment {
+        slug: "integer-op-costs",
+        target: "integer",
+        question: "How much more do multiplication, division and modulo cost than addition, per width?",
+        pattern: "int_{variant}_{case}/plain",
+        variants: &["add", "mul", "div", "rem"],
+        baseline: "add",
+    },
+    Experiment {
+        slug: "layered-moves",
+        target: "layers_move",
+        question: "Does moving a payload through three layers cost more than through one?",
+        pattern: "layer_moves/{case}/{variant}/*",
+        variants: &[
+            "Payload_1_layer",
+            "Payload_3_layers",
+            "PayloadBig_1_layer",
+            "PayloadBig_3_layers",
+            "Payload_forward_only_1_layer",
+            "Payload_forward_only_3_layers",
+            "PayloadBig_forward_only_1_layer",
+            "PayloadBig_forward_only_3_layers",
+        ],
+        baseline: "Payload_1_layer",
+    },
+    Experiment {
+        slug: "mapping-move-clone-ref",
+        target: "mapping",
+        question: "Is mapping to V2 by move cheaper than by clone or from a reference?",
+        pattern: "map_{case}_to_v2_{variant}",
+        variants: &["move", "clone", "reference"],
+        baseline: "move",
+    },
+    Experiment {
+        slug: "mapping-cost",
+        target: "mapping_performance_cost",
+        question: "What does a mapping layer add over returning the input unchanged?",
+        pattern: "{case}_{variant}",
+        variants: &["return_input", "map_and_return"],
+        baseline: "return_input",
+    },
+    Experiment {
+        slug: "pass-move-ref-clone",
+        target: "move_reference_pass",
+        question: "Is passing a payload by reference cheaper than moving or cloning it?",
+        pattern: "{variant}_{case}",
+        variants: &[
+            "move",
+            "clone",
+            "pass_by_reference",
+            "pass_by_mutable_reference",
+        ],
+        baseline: "move",
+    },
+    Experiment {
+        slug: "return-move-vs-box",
+        target: "move_reference_return",
+        question: "Is returning a `Box<Payload>` slower than returning it by move?",
+        pattern: "return_{variant}_{case}",
+        variants: &["move", "box"],
+        baseline: "move",
+    },
+    Experiment {
+        slug: "struct-sweep-pod",
+        target: "struct_size_sweep",
+        question: "At what field count does returning a plain-data struct by value stop being free?",
+        pattern: "struct_sweep_pod/{variant}/{case}",
+        variants: &["ref", "move", "box", "clone"],
+        baseline: "ref",
+    },
+    Experiment {
+        slug: "struct-sweep-mixed",
+        target: "struct_size_sweep",
+        question: "At what field count does returning a heap-owning struct by value stop being free?",
+        pattern: "struct_sweep_mixed/{variant}/{case}",
+        variants: &["ref", "move", "box", "clone"],
+        baseline: "ref",
+    },
+    Experiment {
+        slug: "service-get",
+        target: "service_layers",
+        question: "How much does the wiring strategy cost on a full read request?",
+        pattern: "service_get_{case}/{variant}",
+        variants: &["move", "clone", "arc", "box_dyn"],
+        baseline: "move",
+    },
+    Experiment {
+        slug: "service-create",
+        target: "service_layers",
+        question: "How much does the wiring strategy cost on a create-and-delete round trip?",
+        pattern: "service_create_{case}/{variant}",
+        variants: &["move", "clone", "arc", "box_dyn"],
+        baseline: "move",
+    },
+    // dispatch
+    Experiment {
+        slug: "dyn-vs-generic",
+        target: "trait_direct",
+        question: "Is `dyn UserRepo` slower than a generic or a concrete call?",
+        pattern: "{variant}",
+        variants: &[
+            "generic_monomorphized_call",
+            "original_concrete_call",
+            "original_trait_object_call",
+        ],
+        baseline: "generic_monomorphized_call",
+    },
+    Experiment {
+        slug: "dispatch-interleaved",
+        target: "trait_direct",
+        question: "How does dispatch cost grow with the number of implementors when they are mixed?",
+        pattern: "dispatch_interleaved/{variant}/{case}",
+        variants: &["enum_match", "fn_pointer", "box_dyn", "rc_dyn", "arc_dyn"],
+        baseline: "enum_match",
+    },
+    Experiment {
+        slug: "dispatch-sorted",
+        target: "trait_direct",
+        question: "How does dispatch cost grow with the number of implementors when calls are grouped by type?",
+        pattern: "dispatch_sorted/{variant}/{case}",
+        variants: &["enum_match", "fn_pointer", "box_dyn", "rc_dyn", "arc_dyn"],
+        baseline: "enum_match",
+    },
+    Experiment {
+        slug: "error-types",
+        target: "trait_direct",
+        question: "Do `Box<dyn Error>` or enum errors cost more than a concrete error type?",
+        pattern: "{variant}_error_result",
+        variants: &["concrete", "dynamic", "enum"],
+        baseline: "concrete",
+    },
+    Experiment {
+        slug: "error-rate",
+        target: "trait_direct",
+        question: "How does the cost of each error type scale with the share of calls that fail?",
+        pattern: "error_rate/{variant}/{case}",
+        variants: &["concrete", "dynamic", "enum"],
+        baseline: "concrete",
+    },
+    Experiment {
+        slug: "error-depth",
+        target: "error_paths",
+        question: "What does carrying an error up through 1 to 5 `?` layers cost per error type?",
+        pattern: "error_depth/{variant}/{case}",
+        variants: &["enum", "thin_box", "boxed_dyn", "context_chain"],
+        baseline: "enum",
+    },
+    Experiment {
+        slug: "error-render",
+        target: "error_paths",
+        question: "How much does formatting the error and its source chain add on top?",
+        pattern: "error_render/{variant}/{case}",
+        variants: &["enum", "boxed_dyn", "context_chain"],
+        baseline: "enum",
+    },
+    Experiment {
+        slug: "unwind-happy-path",
+        target: "panic_unwind",
+        question: "Does a `catch_unwind` boundary that never fires cost anything?",
+        pattern: "unwind_happy_path/{variant}",
+        variants: &["direct", "result", "catch_unwind"],
+        baseline: "direct",
+    },
+    Experiment {
+        slug: "unwind-depth",
+        target: "panic_unwind",
+        question: "How does a caught panic compare with returning `Err` through the same frames?",
+        pattern: "unwind_depth/{variant}/{case}",
+        variants: &["err", "panic"],
+        baseline: "err",
+    },
+    Experiment {
+        slug: "async-dispatch",
+        target: "async_dispatch",
+        question: "What does a dyn-compatible async repository cost over native `async fn` in traits?",
+        pattern: "async_{case}/{variant}",
+        variants: &[
+            "native_generic",
+            "sync_concrete_call",
+            "boxed_dyn",
+            "enum_dispatch",
+        ],
+        baseline: "native_generic",
+    },
+    // iterators
+    Experiment {
+        slug: "loops-vs-iterators",
+        target: "loop_iterator",
+        question: "Are iterator adapters slower than hand-written loops?",
+        pattern: "{case}_{variant}",
+        variants: &["for_loop", "without_sugar"],
+        baseline: "for_loop",
+    },
+    Experiment {
+        slug: "slice-iterators",
+        target: "iterator_slices",
+        question: "Over real slices, do iterator adapters beat bounds-checked indexed loops?",
+        pattern: "slice_{case}/{variant}",
+        variants: &["indexed", "iterator"],
+        baseline: "indexed",
+    },
+    // strings
+    Experiment {
+        slug: "string-vs-str",
+        target: "string_str",
+        question: "Is building from `&str` cheaper than from `String`?",
+        pattern: "{case}_{variant}",
+        variants: &["string", "str"],
+        baseline: "string",
+    },
+    Experiment {
+        slug: "string-render",
+        target: "string_building",
+        question: "How should many small pieces be joined into one string?",
+        pattern: "string_render_{case}/{variant}",
+        variants: &["push_str", "write", "format", "concat", "join"],
+        baseline: "push_str",
+    },
+    Experiment {
+        slug: "string-capacity",
+        target: "string_building",
+        question: "Does `String::with_capacity` pay off over growing as you push?",
+        pattern: "string_capacity_{case}/{variant}",
+        variants: &["grow", "with_capacity"],
+        baseline: "grow",
+    },
+    Experiment {
+        slug: "string-to-owned",
+        target: "string_building",
+        question: "Do `to_string`, `to_owned`, `into` and `String::from` cost the same?",
+        pattern: "string_owned_{case}/{variant}",
+        variants: &["to_owned", "to_string", "into", "string_from", "format"],
+        baseline: "to_owned",
+    },
+    Experiment {
+        slug: "string-shared",
+        target: "string_building",
+        question: "What do `String`, `Box<str>`, `Arc<str>` and `Cow<str>` cost to build and to clone?",
+        pattern: "string_shared_{case}/{variant}",
+        variants: &["string", "box_str", "arc_str", "cow_borrowed", "cow_owned"],
+        baseline: "string",
+    },
+    Experiment {
+        slug: "number-formatting",
+        target: "string_building",
+        question: "What is the cheapest way to turn numbers into text?",
+        pattern: "string_number_{case}/{variant}",
+        variants: &["to_string", "format", "write", "manual", "precision"],
+        baseline: "to_string",
+    },
+    // algorithms
+    Experiment {
+        slug: "sort-algorithms",
+        target: "sorting",
+        question: "Which sort suits each element type and input order?",
+        pattern: "sort_{case}/{variant}",
+        variants: &[
+            "sort_unstable",
+            "sort",
+            "sort_by_key",
+            "sort_by_cached_key",
+            "select_nth_unstable",
+            "radix",
+        ],
+        baseline: "sort_unstable",
+    },
+    Experiment {
+        slug: "search-small",
+        target: "sorting",
+        question: "Does binary search beat a linear scan over 16 sorted elements?",
+        pattern: "search_{case}/{variant}/16",
+        variants: &["binary_search", "partition_point", "linear", "eytzinger"],
+        baseline: "binary_search",
+    },
+    Experiment {
+        slug: "search-large",
+        target: "sorting",
+        question: "Does an Eytzinger layout beat binary search over 64k sorted elements?",
+        pattern: "search_{case}/{variant}/65536",
+        variants: &["binary_search", "partition_point", "linear", "eytzinger"],
+        baseline: "binary_search",
+    },
+    Experiment {
+        slug: "simd",
+        target: "simd",
+        question: "Do manual unrolling and SSE2/AVX2 intrinsics beat the loops LLVM vectorises?",
+        pattern: "simd_{case}/{variant}",
+        variants: &["scalar", "iterator", "chunks_exact", "sse2", "avx2"],
+        baseline: "scalar",
+    },
+    Experiment {
+        slug: "float-sum",
+        target: "float_reductions",
+        question: "What do pairwise and Kahan summation cost over a naive float sum?",
+        pattern: "float_sum_{case}/{variant}",
+        variants: &["naive", "sum", "fold", "pairwise", "kahan"],
+        baseline: "naive",
+    },
+    Experiment {
+        slug: "float-dot",
+        target: "float_reductions",
+        question: "Is a dot product with `mul_add` slower than multiply-then-add?",
+        pattern: "float_dot_{case}/{variant}",
+        variants: &["mul_then_add", "mul_add"],
+        baseline: "mul_then_add",
+    },
+];
+
+/// Results of one experiment, arranged as cases × variants.
+#[derive(Debug)]
+pub struct Findings<'a> {
+    pub experiment: &'a Experiment,
+    /// Case names in first-seen order; `""` when the pattern has no `{case}`.
+    pub cases: Vec<String>,
+    /// `cells[case][variant]`, indexed like `cases` and `experiment.variants`.
+    pub cells: Vec<Vec<Option<Estimate>>>,
+    /// Relative error of each cell's computed value, for benches that
+    /// record one; indexed like `cells`.
+    pub errors: Vec<Vec<Option<f64>>>,
+}
+
+impl Experiment {
+    /// Splits `id` into `(case, variant index)` if it belongs to this experiment.
+    ///
+    /// Longer variants are tried first so `mut_ref` is not read as case
+    /// `…_mut` plus variant `ref`.
+    pub fn classify(&self, id: &str) -> Option<(String, usize)> {
+        let mut order: Vec<usize> = (0..self.variants.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.variants[i].len()));
+        order.into_iter().find_map(|i| {
+            let pattern = self.pattern.replace("{variant}", self.variants[i]);
+            let mut case = None;
+            glob(&pattern, id, &mut case).then(|| (case.unwrap_or_default(), i))
+        })
+    }
+
+    /// Picks this experiment's ids out of `results`.
+    pub fn findings<'a>(&'a self, results: &[BenchResult]) -> Findings<'a> {
+        let mut findings = Findings {
+            experiment: self,
+            cases: Vec::new(),
+            cells: Vec::new(),
+            errors: Vec::new(),
+        };
+        for r in results {
+            let Some((case, variant)) = self.classify(&r.id) else {
+                continue;
+            };
+            let row = match findings.cases.iter().position(|c| *c == case) {
+                Some(row) => row,
+                None => {
+                    findings.cases.push(case);
+                    findings.cells.push(vec![None; self.variants.len()]);
+                    findings.errors.push(vec![None; self.variants.len()]);
+                    findings.cases.len() - 1
+                }
+            };
+            findings.cells[row][variant] = Some(r.mean);
+            findings.errors[row][variant] = r.accuracy.map(|a| a.relative);
+        }
+
+        let mut order: Vec<usize> = (0..findings.cases.len()).collect();
+        order.sort_by_key(|&i| case_key(&findings.cases[i]));
+        findings.cases = order.iter().map(|&i| findings.cases[i].clone()).collect();
+        findings.cells = order.iter().map(|&i| findings.cells[i].clone()).collect();
+        findings.errors = order.iter().map(|&i| findings.errors[i].clone()).collect();
+        findings
+    }
+
+    fn baseline_index(&self) -> usize {
+        self.variants
+            .iter()
+            .position(|v| *v == self.baseline)
+            .expect("an experiment's baseline is one of its variants")
+    }
+}
+
+/// Matches `text` against a pattern where `*` matches any non-empty run of
+/// characters and `{case}` any non-empty run without a `/`; stores what
+/// `{case}` matched.
+fn glob(pattern: &str, text: &str, case: &mut Option<String>) -> bool {
+    let (literal, wildcard, rest) = match (pattern.find("{case}"), pattern.find('*')) {
+        (Some(c), Some(s)) if s < c => (&pattern[..s], "*", &pattern[s + 1..]),
+        (Some(c), _) => (&pattern[..c], "{case}", &pattern[c + "{case}".len()..]),
+        (None, Some(s)) => (&pattern[..s], "*", &pattern[s + 1..]),
+        (None, None) => return pattern == text,
+    };
+    let Some(text) = text.strip_prefix(literal).filter(|t| !t.is_empty()) else {
+        return false;
+    };
+    // Shortest match first: the literal after a wildcard is usually a
+    // separator that the wildcard itself never contains.
+    for (end, _) in text.char_indices().skip(1).chain([(text.len(), ' ')]) {
+        // Keep `{case}` inside one id segment so counter-prefixed ids such
+        // as `instructions/i64::…` do not pass for wall-clock cases.
+        if wildcard == "{case}" && text[..end].contains('/') {
+            break;
+        }
+        if glob(rest, &text[end..], case) {
+            if wildcard == "{case}" {
+                *case = Some(text[..end].to_string());
+            }
+            return true;
+        }
+    }
+    false
+}
+
+/// Sorts cases the way the benches declare them: by the first number in
+/// the name (`N=10` before `N=100`), then small < medium < large, then by
+/// name.
+fn case_key(case: &str) -> (u64, u8, String) {
+    let digits: String = case
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    let size = ["small", "medium", "large"]
+        .iter()
+        .position(|s| case.contains(s))
+        .map_or(u8::MAX, |i| i as u8);
+    (digits.parse().unwrap_or(0), size, case.to_string())
+}
+
+/// Whether two estimates' confidence intervals are disjoint.
+fn significant(a: &Estimate, b: &Estimate) -> bool {
+    a.upper < b.lower || b.upper < a.lower
+}
+
+impl Findings<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+
+    /// One sentence per non-baseline variant.
+    pub fn verdict(&self) -> Vec<String> {
+        let e = self.experiment;
+        let base = e.baseline_index();
+        let mut out = Vec::new();
+
+        for (v, name) in e.variants.iter().enumerate() {
+            if v == base {
+                continue;
+            }
+            let (mut faster, mut slower, mut same) = (0, 0, 0);
+            let mut speedups = Vec::new();
+            for row in &self.cells {
+                let (Some(b), Some(x)) = (row[base], row[v]) else {
+                    continue;
+                };
+                speedups.push(b.point / x.point);
+                if !significant(&b, &x) {
+                    same += 1;
+                } else if x.point < b.point {
+                    faster += 1;
+                } else {
+                    slower += 1;
+                }
+            }
+            if speedups.is_empty() {
+                continue;
+            }
+            let total = speedups.len();
+            let lo = speedups.iter().copied().fold(f64::INFINITY, f64::min);
+            let hi = speedups.iter().copied().fold(0.0, f64::max);
+            let range = if total == 1 {
+                format!("{lo:.2}×")
+            } else {
+                format!("{lo:.2}×–{hi:.2}×")
+            };
+            let summary = if faster + slower == 0 {
+                format!("no significant difference in {total} cases")
+            } else {
+                let parts: Vec<String> = [
+                    (faster, "faster"),
+                    (slower, "slower"),
+                    (same, "within noise"),
+                ]
+                .into_iter()
+                .filter(|(n, _)| *n > 0)
+                .map(|(n, what)| format!("{what} in {n}"))
+                .collect();
+                format!("{} of {total} cases", parts.join(", "))
+            };
+            out.push(format!(
+                "`{name}` vs `{}`: {summary} ({range}).",
+                e.baseline
+            ));
+        }
+        out
+    }
+
+    /// Renders the page: question, verdict, then a cases × variants table
+    /// of means with speedups relative to the baseline, followed by each
+    /// variant's relative error when the bench recorded any.
+    pub fn to_markdown(&self) -> String {
+        let e = self.experiment;
+        let base = e.baseline_index();
+        let has_errors = self.errors.iter().flatten().any(Option::is_some);
+        let mut md = String::new();
+
+        let _ = writeln!(md, "# {}\n", e.question);
+        let _ = writeln!(
+            md,
+            "Bench target `{}`; speedups are relative to `{}` (higher is faster). \
+             A difference counts only when the 95% confidence intervals do not overlap.\n",
+            e.target, e.baseline
+        );
+
+        let _ = writeln!(md, "## Verdict\n");
+        for line in self.verdict() {
+            let _ = writeln!(md, "- {line}");
+        }
+
+        let _ = writeln!(md, "\n## Results\n");
+        let _ = write!(md, "| case |");
+        for (v, name) in e.variants.iter().enumerate() {
+            let marker = if v == base { " (baseline)" } else { "" };
+            let _ = write!(md, " `{name}`{marker} |");
+        }
+        if has_errors {
+            for name in e.variants {
+                let _ = write!(md, " `{name}` rel. error |");
+            }
+        }
+        let _ = write!(md, "\n|---|");
+        let columns = if has_errors { 2 } else { 1 } * e.variants.len();
+        for _ in 0..columns {
+            let _ = write!(md, "---:|");
+        }
+        md.push('\n');
+
+        for ((case, row), errors) in self.cases.iter().zip(&self.cells).zip(&self.errors) {
+            let label = if case.is_empty() { "—" } else { case };
+            let _ = write!(md, "| {} |", label.replace('|', "\\|"));
+            for (v, cell) in row.iter().enumerate() {
+                let text = match (cell, row[base]) {
+                    (None, _) => "–".to_string(),
+                    (Some(x), _) if v == base => format_ns(x.point),
+                    (Some(x), Some(b)) => {
+                        let mark = if significant(&b, x) { "" } else { " ~" };
+                        format!("{} ({:.2}×{mark})", format_ns(x.point), b.point / x.point)
+                    }
+                    (Some(x), None) => format_ns(x.point),
+                };
+                let _ = write!(md, " {text} |");
+            }
+            if has_errors {
+                for error in errors {
+                    match error {
+                        Some(rel) => {
+                            let _ = write!(md, " {rel:.1e} |");
+                        }
+                        None => md.push_str(" – |"),
+                    }
+                }
+            }
+            md.push('\n');
+        }
+        let _ = writeln!(md, "\n`~` marks results within noise of the baseline.");
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suites;
+
+    fn case_of(pattern: &str, id: &str) -> Option<String> {
+        let mut case = None;
+        glob(pattern, id, &mut case).then(|| case.unwrap_or_default())
+    }
+
+    #[test]
+    fn glob_captures_the_case() {
+        assert_eq!(case_of("int_{case}/plain", "int_i64/plain").unwrap(), "i64");
+        assert_eq!(
+            case_of("map_{case}_to_v2", "map_small_to_v2").unwrap(),
+            "small"
+        );
+        assert_eq!(case_of("exact", "exact").unwrap(), "");
+        assert_eq!(case_of("int_{case}/plain", "int_/plain"), None);
+        assert_eq!(case_of("int_{case}/plain", "int_i64/plainer"), None);
+        assert_eq!(case_of("exact", "exactly"), None);
+    }
+
+    #[test]
+    fn glob_star_spans_segments() {
+        assert_eq!(
+            case_of(
+                "return_overhead_{case}/id/*",
+                "return_overhead_[u8;10]/id/10"
+            )
+            .unwrap(),
+            "[u8;10]"
+        );
+        assert_eq!(case_of("layer_moves/*", "layer_moves/a/b/c").unwrap(), "");
+        assert_eq!(case_of("layer_moves/*", "layer_moves/"), None);
+    }
+
+    #[test]
+    fn glob_case_stays_in_one_segment() {
+        // A counter label in front of the id must not become part of the case.
+        assert_eq!(case_of("{case}::plain", "instructions/i64::plain"), None);
+        assert_eq!(case_of("{case}::plain", "i64::plain").unwrap(), "i64");
+        assert_eq!(
+            case_of("{case}_error_result", "cycles/string_error_result"),
+            None
+        );
+    }
+
+    #[test]
+    fn classify_prefers_longer_variants() {
+        let e = Experiment {
+            slug: "test",
+            target: "test",
+            question: "",
+            pattern: "{case}_{variant}",
+            variants: &["ref", "mut_ref"],
+            baseline: "ref",
+        };
+        assert_eq!(e.classify("small_mut_ref"), Some(("small".to_string(), 1)));
+        assert_eq!(e.classify("small_ref"), Some(("small".to_string(), 0)));
+        assert_eq!(e.classify("cycles/small_ref"), None);
+    }
+
+    #[test]
+    fn unsuffixed_sip_ids_stay_their_own_variant() {
+        let e = EXPERIMENTS
+            .iter()
+            .find(|e| e.slug == "map-vs-sorted-vec")
+            .unwrap();
+        let variant = |id| e.classify(id).map(|(case, v)| (case, e.variants[v]));
+        assert_eq!(
+            variant("map_search_100 elements"),
+            Some(("100".to_string(), "map_search"))
+        );
+        assert_eq!(
+            variant("map_search_fx_100 elements"),
+            Some(("100".to_string(), "map_search_fx"))
+        );
+    }
+
+    #[test]
+    fn experiments_are_well_formed() {
+        for e in EXPERIMENTS {
+            assert!(e.variants.contains(&e.baseline), "{}", e.slug);
+            assert!(e.pattern.contains("{variant}"), "{}", e.slug);
+            assert!(suites::bench(e.target).is_some(), "{}", e.slug);
+        }
+    }
+}
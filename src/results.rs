@@ -0,0 +1,614 @@
+//! Collects Criterion's saved estimates into one machine-readable run.
+//!
+//! Criterion leaves a `new/benchmark.json` + `new/estimates.json` pair for
+//! every benchmark id it measured, nested under `target/criterion` by group,
+//! function and parameter. [`collect`] walks that tree, and [`Run`] bundles
+//! the results with an [`Environment`] snapshot so runs from different
+//! commits or machines can be archived side by side.
+//!
+//! How each bench binary was built, and which data seed it ran with, is
+//! only known to the binary itself, so [`bench_main!`](crate::bench_main)
+//! records a [`BuildInfo`] next to the results when it starts, and the
+//! export reads those back.
+//!
+//! Benches whose variants trade accuracy for speed record each id's
+//! [`Accuracy`] the same way, as `accuracy.json` in the id's directory.
+//!
+//! Estimates are in the unit of the measurement that produced them:
+//! nanoseconds for wall-clock groups, counts for `perf`-counted ones.
+
+use crate::data;
+use crate::perf;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A point estimate with its confidence interval.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    pub point: f64,
+    pub lower: f64,
+    pub upper: f64,
+    pub standard_error: f64,
+}
+
+/// Work done per iteration, as declared with `group.throughput(..)`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Throughput {
+    /// `bytes` or `elements`.
+    pub kind: String,
+    pub per_iteration: u64,
+    /// `per_iteration` divided by the mean time; absent for ids counted in
+    /// hardware events rather than nanoseconds.
+    pub per_second: Option<f64>,
+}
+
+/// One benchmark id from one Criterion run.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    /// Full id as Criterion prints it, e.g. `layer_moves/bytes=1024`.
+    pub id: String,
+    pub group: String,
+    pub function: Option<String>,
+    pub parameter: Option<String>,
+    pub mean: Estimate,
+    pub median: Estimate,
+    pub std_dev: Estimate,
+    /// Only present for linear sampling, which most groups use.
+    pub slope: Option<Estimate>,
+    pub throughput: Option<Throughput>,
+    /// Error of the value the id computed, if its bench recorded one.
+    #[serde(default)]
+    pub accuracy: Option<Accuracy>,
+}
+
+/// How far the value a benchmark computed is from an exact reference.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Accuracy {
+    /// `absolute` divided by the magnitude of the reference.
+    pub relative: f64,
+    pub absolute: f64,
+}
+
+/// Where and how a run was produced.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Environment {
+    pub git_commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub git_dirty: bool,
+    /// `rustc -V`.
+    pub rustc: Option<String>,
+    pub cpu: Option<String>,
+    pub os: String,
+    pub arch: String,
+    /// `target_feature`s of the bench builds; empty if they differ.
+    pub target_features: Vec<String>,
+    /// `opt-level` of the bench builds; `None` if they differ or none was recorded.
+    pub opt_level: Option<String>,
+    /// `RUSTFLAGS` of the bench builds; `None` if they differ or none was recorded.
+    #[serde(default)]
+    pub rustflags: Option<String>,
+    /// Every bench build recorded under `target/criterion`, by target.
+    #[serde(default)]
+    pub builds: Vec<BuildInfo>,
+    /// Seconds since the Unix epoch when the run was exported.
+    pub exported_at: u64,
+    /// Base seed the bench builds generated their [`data`] with; `None` if
+    /// they differ or none was recorded.
+    #[serde(default)]
+    pub data_seed: Option<u64>,
+}
+
+/// How one bench binary was built, recorded by the binary when it runs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// Bench target, e.g. `integer`.
+    pub target: String,
+    pub opt_level: String,
+    /// `target_feature`s the binary was compiled with, after `RUSTFLAGS`.
+    pub target_features: Vec<String>,
+    pub rustflags: String,
+    /// [`data::base_seed`] of the run, which `BENCH_SEED` sets at bench
+    /// time rather than at build time; absent in older records.
+    #[serde(default)]
+    pub data_seed: Option<u64>,
+}
+
+impl BuildInfo {
+    /// The build of the running binary, whose bench target is `target`.
+    pub fn current(target: &str) -> BuildInfo {
+        let features = env!("BENCH_TARGET_FEATURES");
+        BuildInfo {
+            target: target.to_string(),
+            opt_level: env!("BENCH_OPT_LEVEL").to_string(),
+            target_features: features
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(str::to_string)
+                .collect(),
+            rustflags: env!("BENCH_RUSTFLAGS").to_string(),
+            data_seed: Some(data::base_seed()),
+        }
+    }
+
+    fn path(dir: &Path, target: &str) -> PathBuf {
+        dir.join(format!("{target}.build.json"))
+    }
+}
+
+/// Writes the running binary's [`BuildInfo`] into the Criterion directory;
+/// called by [`bench_main!`](crate::bench_main). Failing to write only warns,
+/// since the results themselves are unaffected.
+pub fn record_build(target: &str) {
+    let dir = criterion_dir(Path::new(env!("CARGO_MANIFEST_DIR")));
+    let info = BuildInfo::current(target);
+    let json = serde_json::to_string_pretty(&info).expect("a BuildInfo always serializes");
+    let written =
+        fs::create_dir_all(&dir).and_then(|_| fs::write(BuildInfo::path(&dir, target), json));
+    if let Err(e) = written {
+        eprintln!(
+            "warning: cannot record the build of `{target}` under {}: {e}",
+            dir.display()
+        );
+    }
+}
+
+/// Writes `accuracy` next to Criterion's files for benchmark `id`, where
+/// [`collect`] picks it up. Like [`record_build`], failing only warns.
+pub fn record_accuracy(id: &str, accuracy: Accuracy) {
+    let dir = criterion_dir(Path::new(env!("CARGO_MANIFEST_DIR"))).join(id);
+    let json = serde_json::to_string_pretty(&accuracy).expect("an Accuracy always serializes");
+    let written = fs::create_dir_all(&dir).and_then(|_| fs::write(dir.join(ACCURACY_FILE), json));
+    if let Err(e) = written {
+        eprintln!(
+            "warning: cannot record the accuracy of `{id}` under {}: {e}",
+            dir.display()
+        );
+    }
+}
+
+const ACCURACY_FILE: &str = "accuracy.json";
+
+/// Reads every [`BuildInfo`] recorded under `dir`, sorted by target.
+pub fn collect_builds(dir: &Path) -> Vec<BuildInfo> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut out: Vec<BuildInfo> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.to_str().is_some_and(|p| p.ends_with(".build.json")))
+        .filter_map(|p| read_json(&p).ok())
+        .collect();
+    out.sort_by(|a, b| a.target.cmp(&b.target));
+    out
+}
+
+/// The value every build agrees on, if any build was recorded.
+fn shared<T: PartialEq + Clone>(
+    builds: &[BuildInfo],
+    field: impl Fn(&BuildInfo) -> &T,
+) -> Option<T> {
+    let first = field(builds.first()?);
+    builds
+        .iter()
+        .all(|b| field(b) == first)
+        .then(|| first.clone())
+}
+
+/// One exported run: environment plus every collected result.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    pub environment: Environment,
+    pub results: Vec<BenchResult>,
+}
+
+/// The directory Criterion writes to for a project rooted at `manifest_dir`.
+///
+/// Honours `CRITERION_HOME` and `CARGO_TARGET_DIR` the same way Criterion
+/// and Cargo do.
+pub fn criterion_dir(manifest_dir: &Path) -> PathBuf {
+    if let Some(home) = env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    let target = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest_dir.join("target"));
+    target.join("criterion")
+}
+
+/// Reads every result of the latest run under `dir`, sorted by id.
+pub fn collect(dir: &Path) -> Result<Vec<BenchResult>, String> {
+    collect_baseline(dir, "new")
+}
+
+/// Reads every result saved under baseline `name` (as in
+/// `--save-baseline name`), sorted by id. The latest run is called `new`.
+pub fn collect_baseline(dir: &Path, name: &str) -> Result<Vec<BenchResult>, String> {
+    let mut out = Vec::new();
+    walk(dir, name, &mut out)?;
+    out.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(out)
+}
+
+fn walk(dir: &Path, baseline: &str, out: &mut Vec<BenchResult>) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("cannot read {}: {e}", dir.display()))?;
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let name = path.file_name().and_then(|n| n.to_str());
+        if path.join("estimates.json").is_file() {
+            // A saved baseline; other ones (and `change/`) are not ours.
+            if name == Some(baseline) {
+                out.push(read_result(&path)?);
+            }
+        } else if name != Some("report") {
+            walk(&path, baseline, out)?;
+        }
+    }
+    Ok(())
+}
+
+// The parts of Criterion's own JSON files that we use.
+
+#[derive(Deserialize)]
+struct RawBenchmark {
+    group_id: String,
+    function_id: Option<String>,
+    value_str: Option<String>,
+    throughput: Option<RawThroughput>,
+    full_id: String,
+}
+
+#[derive(Deserialize)]
+enum RawThroughput {
+    Bytes(u64),
+    BytesDecimal(u64),
+    Elements(u64),
+}
+
+#[derive(Deserialize)]
+struct RawEstimates {
+    mean: RawEstimate,
+    median: RawEstimate,
+    std_dev: RawEstimate,
+    slope: Option<RawEstimate>,
+}
+
+#[derive(Deserialize)]
+struct RawEstimate {
+    confidence_interval: RawInterval,
+    point_estimate: f64,
+    standard_error: f64,
+}
+
+#[derive(Deserialize)]
+struct RawInterval {
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+impl From<RawEstimate> for Estimate {
+    fn from(raw: RawEstimate) -> Estimate {
+        Estimate {
+            point: raw.point_estimate,
+            lower: raw.confidence_interval.lower_bound,
+            upper: raw.confidence_interval.upper_bound,
+            standard_error: raw.standard_error,
+        }
+    }
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    serde_json::from_str(&text).map_err(|e| format!("cannot parse {}: {e}", path.display()))
+}
+
+fn read_result(new_dir: &Path) -> Result<BenchResult, String> {
+    let bench: RawBenchmark = read_json(&new_dir.join("benchmark.json"))?;
+    let estimates: RawEstimates = read_json(&new_dir.join("estimates.json"))?;
+
+    // Shared by every baseline of the id, so it sits one level up.
+    let accuracy = match new_dir.parent().map(|d| d.join(ACCURACY_FILE)) {
+        Some(path) if path.is_file() => Some(read_json(&path)?),
+        _ => None,
+    };
+
+    let mean = Estimate::from(estimates.mean);
+    let throughput = bench.throughput.map(|t| {
+        let (kind, n) = match t {
+            RawThroughput::Bytes(n) | RawThroughput::BytesDecimal(n) => ("bytes", n),
+            RawThroughput::Elements(n) => ("elements", n),
+        };
+        Throughput {
+            kind: kind.to_string(),
+            per_iteration: n,
+            per_second: perf::counter_of(&bench.full_id)
+                .is_none()
+                .then(|| n as f64 / mean.point * 1e9),
+        }
+    });
+
+    Ok(BenchResult {
+        id: bench.full_id,
+        group: bench.group_id,
+        function: bench.function_id,
+        parameter: bench.value_str,
+        mean,
+        median: estimates.median.into(),
+        std_dev: estimates.std_dev.into(),
+        slope: estimates.slope.map(Estimate::from),
+        throughput,
+        accuracy,
+    })
+}
+
+// -----------------------------
+// Environment
+// -----------------------------
+
+impl Environment {
+    /// Captures the environment for the project rooted at `manifest_dir`,
+    /// with the builds its benches recorded in `criterion_dir`.
+    ///
+    /// Anything that cannot be determined (no git, no `/proc/cpuinfo`) is
+    /// left as `None` rather than failing the export.
+    pub fn capture(manifest_dir: &Path, criterion_dir: &Path) -> Environment {
+        let git =
+            |args: &[&str]| output(Command::new("git").arg("-C").arg(manifest_dir).args(args));
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        let builds = collect_builds(criterion_dir);
+
+        Environment {
+            git_commit: git(&["rev-parse", "HEAD"]),
+            git_dirty: git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()),
+            rustc: output(Command::new(&rustc).arg("-V")),
+            cpu: cpu_model(),
+            os: env::consts::OS.to_string(),
+            arch: env::consts::ARCH.to_string(),
+            target_features: shared(&builds, |b| &b.target_features).unwrap_or_default(),
+            opt_level: shared(&builds, |b| &b.opt_level),
+            rustflags: shared(&builds, |b| &b.rustflags),
+            data_seed: shared(&builds, |b| &b.data_seed).flatten(),
+            builds,
+            exported_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+/// Trimmed stdout of `cmd`, or `None` if it could not run or failed.
+fn output(cmd: &mut Command) -> Option<String> {
+    let out = cmd.output().ok()?;
+    out.status
+        .success()
+        .then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+fn cpu_model() -> Option<String> {
+    let info = fs::read_to_string("/proc/cpuinfo").ok()?;
+    info.lines()
+        .find(|l| l.starts_with("model name"))
+        .and_then(|l| l.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
+}
+
+// -----------------------------
+// Output
+// -----------------------------
+
+const CSV_HEADER: &[&str] = &[
+    "id",
+    "group",
+    "function",
+    "parameter",
+    "mean",
+    "mean_lower",
+    "mean_upper",
+    "median",
+    "std_dev",
+    "throughput_kind",
+    "throughput_per_iteration",
+    "throughput_per_second",
+    "relative_error",
+    "git_commit",
+    "git_dirty",
+    "rustc",
+    "cpu",
+    "target_features",
+    "opt_level",
+    "rustflags",
+    "exported_at",
+    "data_seed",
+];
+
+impl Run {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a Run always serializes")
+    }
+
+    pub fn from_json(text: &str) -> Result<Run, String> {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    }
+
+    /// One row per result; environment columns repeat on every row so each
+    /// row stands on its own once files from several runs are concatenated.
+    pub fn to_csv(&self) -> String {
+        let env = &self.environment;
+        let mut out = CSV_HEADER.join(",");
+        out.push('\n');
+        for r in &self.results {
+            let t = r.throughput.as_ref();
+            let row = [
+                r.id.clone(),
+                r.group.clone(),
+                r.function.clone().unwrap_or_default(),
+                r.parameter.clone().unwrap_or_default(),
+                r.mean.point.to_string(),
+                r.mean.lower.to_string(),
+                r.mean.upper.to_string(),
+                r.median.point.to_string(),
+                r.std_dev.point.to_string(),
+                t.map(|t| t.kind.clone()).unwrap_or_default(),
+                t.map(|t| t.per_iteration.to_string()).unwrap_or_default(),
+                t.and_then(|t| t.per_second)
+                    .map(|p| p.to_string())
+                    .unwrap_or_default(),
+                r.accuracy
+                    .map(|a| a.relative.to_string())
+                    .unwrap_or_default(),
+                env.git_commit.clone().unwrap_or_default(),
+                env.git_dirty.to_string(),
+                env.rustc.clone().unwrap_or_default(),
+                env.cpu.clone().unwrap_or_default(),
+                env.target_features.join(" "),
+                env.opt_level.clone().unwrap_or_default(),
+                env.rustflags.clone().unwrap_or_default(),
+                env.exported_at.to_string(),
+                env.data_seed.map(|s| s.to_string()).unwrap_or_default(),
+            ];
+            let row: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    fn estimate(point: f64) -> String {
+        format!(
+            r#"{{"confidence_interval":{{"confidence_level":0.95,"lower_bound":{},"upper_bound":{}}},"point_estimate":{point},"standard_error":1.0}}"#,
+            point - 1.0,
+            point + 1.0
+        )
+    }
+
+    /// Writes one Criterion result directory the way Criterion 0.5 does.
+    fn write_result(dir: &Path, full_id: &str, throughput: &str, mean: f64) {
+        fs::create_dir_all(dir).unwrap();
+        let (group, function) = full_id.split_once('/').unwrap();
+        fs::write(
+            dir.join("benchmark.json"),
+            format!(
+                r#"{{"group_id":"{group}","function_id":"{function}","value_str":null,"throughput":{throughput},"full_id":"{full_id}","directory_name":"x","title":"{full_id}"}}"#
+            ),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("estimates.json"),
+            format!(
+                r#"{{"mean":{m},"median":{m},"median_abs_dev":{m},"slope":null,"std_dev":{m}}}"#,
+                m = estimate(mean)
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn collect_reads_the_named_baseline_only() {
+        let dir = env::temp_dir().join(format!("rust_examples_{}_collect", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        write_result(
+            &dir.join("sum/plain/new"),
+            "sum/plain",
+            r#"{"Elements":1000}"#,
+            500.0,
+        );
+        write_result(
+            &dir.join("sum/plain/base"),
+            "sum/plain",
+            r#"{"Elements":1000}"#,
+            900.0,
+        );
+        write_result(
+            &dir.join("instructions_sum/plain/new"),
+            "instructions/sum/plain",
+            r#"{"Bytes":64}"#,
+            2000.0,
+        );
+        fs::write(
+            dir.join("sum/plain/accuracy.json"),
+            r#"{"relative":0.5,"absolute":2.0}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join("report")).unwrap();
+        fs::write(dir.join("integer.build.json"), "{}").unwrap();
+
+        let results = collect(&dir).unwrap();
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["instructions/sum/plain", "sum/plain"]);
+
+        let counted = &results[0];
+        assert_eq!(counted.mean.point, 2000.0);
+        let t = counted.throughput.as_ref().unwrap();
+        assert_eq!(
+            (t.kind.as_str(), t.per_iteration, t.per_second),
+            ("bytes", 64, None)
+        );
+
+        let timed = &results[1];
+        assert_eq!(timed.group, "sum");
+        assert_eq!(timed.function.as_deref(), Some("plain"));
+        assert_eq!((timed.mean.lower, timed.mean.upper), (499.0, 501.0));
+        let t = timed.throughput.as_ref().unwrap();
+        assert_eq!(t.per_second, Some(1000.0 / 500.0 * 1e9));
+        assert_eq!(
+            timed.accuracy,
+            Some(Accuracy {
+                relative: 0.5,
+                absolute: 2.0
+            })
+        );
+        assert_eq!(counted.accuracy, None);
+
+        let base = collect_baseline(&dir, "base").unwrap();
+        assert_eq!(base.len(), 1);
+        assert_eq!(base[0].mean.point, 900.0);
+        assert_eq!(base[0].accuracy, timed.accuracy);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn shared_is_none_when_builds_differ() {
+        let build = |opt: &str| BuildInfo {
+            target: "t".to_string(),
+            opt_level: opt.to_string(),
+            target_features: Vec::new(),
+            rustflags: String::new(),
+            data_seed: None,
+        };
+        assert_eq!(shared(&[], |b| &b.opt_level), None);
+        assert_eq!(
+            shared(&[build("3"), build("3")], |b| &b.opt_level),
+            Some("3".to_string())
+        );
+        assert_eq!(shared(&[build("3"), build("2")], |b| &b.opt_level), None);
+    }
+}
@@ -0,0 +1,73 @@
+//! A small layered user service for end-to-end ownership benches.
+//!
+//! The layers mirror how our services are wired: a [`UserController`]
+//! receives DTOs, a [`UserUseCase`] works on domain types and a
+//! [`UserRepo`] stores entities, either in memory or in a file. Mappers
+//! between the three type families come in by-value (move) and by-reference
+//! (clone) flavours so benches can compare the two paths through the stack.
+
+mod controller;
+mod mapper;
+mod model;
+mod repo;
+mod usecase;
+
+pub use controller::UserController;
+pub use model::{CreateUserRequest, Email, NewUser, User, UserEntity, UserId, UserResponse};
+pub use repo::{FileUserRepo, InMemoryUserRepo, UserRepo};
+pub use usecase::UserUseCase;
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum ServiceError {
+    NotFound(UserId),
+    /// Request data that failed validation.
+    Invalid(String),
+    Storage(io::Error),
+    /// A stored record that could not be decoded.
+    Corrupt(String),
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::NotFound(id) => write!(f, "user {} not found", id.0),
+            ServiceError::Invalid(why) => write!(f, "invalid request: {why}"),
+            ServiceError::Storage(e) => write!(f, "storage error: {e}"),
+            ServiceError::Corrupt(why) => write!(f, "corrupt record: {why}"),
+        }
+    }
+}
+
+impl Error for ServiceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ServiceError::Storage(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ServiceError {
+    fn from(e: io::Error) -> ServiceError {
+        ServiceError::Storage(e)
+    }
+}
+
+/// Inserts `count` deterministic users so benches have something to read.
+pub fn seed(repo: &impl UserRepo, count: usize) -> Result<Vec<UserId>, ServiceError> {
+    (0..count)
+        .map(|i| {
+            let request = CreateUserRequest {
+                email: format!("user{i}@example.com"),
+                name: format!("User Number {i}"),
+                tags: (0..i % 4 + 1).map(|t| format!("tag{t}")).collect(),
+            };
+            let entity = repo.insert(NewUser::try_from(request)?)?;
+            Ok(UserId(entity.id))
+        })
+        .collect()
+}
@@ -0,0 +1,53 @@
+use super::ServiceError;
+use super::model::{CreateUserRequest, NewUser, UserId, UserResponse};
+use super::repo::UserRepo;
+use super::usecase::UserUseCase;
+
+/// Request handling: DTO in, DTO out.
+#[derive(Clone, Debug)]
+pub struct UserController<R> {
+    use_case: UserUseCase<R>,
+}
+
+impl<R: UserRepo> UserController<R> {
+    pub fn new(use_case: UserUseCase<R>) -> UserController<R> {
+        UserController { use_case }
+    }
+
+    /// Builds the whole stack over `repo`.
+    pub fn wire(repo: R) -> UserController<R> {
+        UserController::new(UserUseCase::new(repo))
+    }
+
+    pub fn use_case(&self) -> &UserUseCase<R> {
+        &self.use_case
+    }
+
+    /// Entity -> domain -> response, moving at every boundary.
+    pub fn get(&self, id: u64) -> Result<UserResponse, ServiceError> {
+        self.use_case.get_user(UserId(id)).map(UserResponse::from)
+    }
+
+    /// Entity -> domain -> response, cloning at every boundary.
+    pub fn get_cloned(&self, id: u64) -> Result<UserResponse, ServiceError> {
+        let user = self.use_case.get_user_cloned(UserId(id))?;
+        Ok(UserResponse::from(&user))
+    }
+
+    pub fn create(&self, request: CreateUserRequest) -> Result<UserResponse, ServiceError> {
+        let user = NewUser::try_from(request)?;
+        self.use_case.create_user(user).map(UserResponse::from)
+    }
+
+    pub fn create_cloned(&self, request: &CreateUserRequest) -> Result<UserResponse, ServiceError> {
+        let user = NewUser::try_from(request)?;
+        let created = self.use_case.create_user_cloned(&user)?;
+        Ok(UserResponse::from(&created))
+    }
+
+    pub fn delete(&self, id: u64) -> Result<UserResponse, ServiceError> {
+        self.use_case
+            .delete_user(UserId(id))
+            .map(UserResponse::from)
+    }
+}
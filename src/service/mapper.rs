@@ -0,0 +1,95 @@
+//! Conversions between the storage, domain and transport types.
+//!
+//! Each mapping exists by value, reusing the source's allocations, and by
+//! reference, cloning every field.
+
+use super::ServiceError;
+use super::model::{CreateUserRequest, Email, NewUser, User, UserEntity, UserId, UserResponse};
+
+impl From<UserEntity> for User {
+    fn from(e: UserEntity) -> User {
+        User {
+            id: UserId(e.id),
+            email: Email::trusted(e.email),
+            name: e.display_name,
+            tags: e.tags,
+            active: e.active,
+            created_at: e.created_at,
+        }
+    }
+}
+
+impl From<&UserEntity> for User {
+    fn from(e: &UserEntity) -> User {
+        User {
+            id: UserId(e.id),
+            email: Email::trusted(e.email.clone()),
+            name: e.display_name.clone(),
+            tags: e.tags.clone(),
+            active: e.active,
+            created_at: e.created_at,
+        }
+    }
+}
+
+impl From<User> for UserResponse {
+    fn from(u: User) -> UserResponse {
+        UserResponse {
+            id: u.id.0,
+            email: u.email.into_string(),
+            name: u.name,
+            tags: u.tags,
+            active: u.active,
+        }
+    }
+}
+
+impl From<&User> for UserResponse {
+    fn from(u: &User) -> UserResponse {
+        UserResponse {
+            id: u.id.0,
+            email: u.email.as_str().to_string(),
+            name: u.name.clone(),
+            tags: u.tags.clone(),
+            active: u.active,
+        }
+    }
+}
+
+impl TryFrom<CreateUserRequest> for NewUser {
+    type Error = ServiceError;
+
+    fn try_from(r: CreateUserRequest) -> Result<NewUser, ServiceError> {
+        Ok(NewUser {
+            email: Email::parse(r.email)?,
+            name: r.name,
+            tags: r.tags,
+        })
+    }
+}
+
+impl TryFrom<&CreateUserRequest> for NewUser {
+    type Error = ServiceError;
+
+    fn try_from(r: &CreateUserRequest) -> Result<NewUser, ServiceError> {
+        Ok(NewUser {
+            email: Email::parse(r.email.clone())?,
+            name: r.name.clone(),
+            tags: r.tags.clone(),
+        })
+    }
+}
+
+impl NewUser {
+    /// The entity a repo stores for this request.
+    pub fn into_entity(self, id: u64, created_at: u64) -> UserEntity {
+        UserEntity {
+            id,
+            email: self.email.into_string(),
+            display_name: self.name,
+            tags: self.tags,
+            active: true,
+            created_at,
+        }
+    }
+}
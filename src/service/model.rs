@@ -0,0 +1,89 @@
+use super::ServiceError;
+
+// -----------------------------
+// Storage
+// -----------------------------
+
+/// A user as a [`UserRepo`](super::UserRepo) stores it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserEntity {
+    pub id: u64,
+    pub email: String,
+    pub display_name: String,
+    pub tags: Vec<String>,
+    pub active: bool,
+    /// Insertion sequence number; the repos keep no wall clock.
+    pub created_at: u64,
+}
+
+// -----------------------------
+// Domain
+// -----------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct UserId(pub u64);
+
+/// An address that passed [`Email::parse`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Email(String);
+
+impl Email {
+    /// Accepts `local@domain` with both parts non-empty.
+    pub fn parse(raw: String) -> Result<Email, ServiceError> {
+        match raw.split_once('@') {
+            Some((local, domain)) if !local.is_empty() && !domain.is_empty() => Ok(Email(raw)),
+            _ => Err(ServiceError::Invalid(format!("`{raw}` is not an email"))),
+        }
+    }
+
+    /// Wraps an address read back from storage, which was validated on insert.
+    pub(crate) fn trusted(raw: String) -> Email {
+        Email(raw)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct User {
+    pub id: UserId,
+    pub email: Email,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub active: bool,
+    pub created_at: u64,
+}
+
+/// A validated creation request; the repo assigns id and sequence number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NewUser {
+    pub email: Email,
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+// -----------------------------
+// Transport
+// -----------------------------
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateUserRequest {
+    pub email: String,
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserResponse {
+    pub id: u64,
+    pub email: String,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub active: bool,
+}
@@ -0,0 +1,372 @@
+use super::ServiceError;
+use super::model::{NewUser, UserEntity, UserId};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Storage for users. Methods take `&self` so one repo can sit behind an
+/// `Arc` and serve several controllers.
+pub trait UserRepo {
+    fn get(&self, id: UserId) -> Result<UserEntity, ServiceError>;
+
+    /// Stores `user` under a fresh id and returns the stored entity.
+    fn insert(&self, user: NewUser) -> Result<UserEntity, ServiceError>;
+
+    /// Deletes the user and returns what was stored.
+    fn remove(&self, id: UserId) -> Result<UserEntity, ServiceError>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// The wiring strategies hold the repo as `R`, `&R`, `Arc<R>` or
+// `Box<dyn UserRepo>`; these let the layers stay generic over all of them.
+
+impl<R: UserRepo + ?Sized> UserRepo for &R {
+    fn get(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        (**self).get(id)
+    }
+    fn insert(&self, user: NewUser) -> Result<UserEntity, ServiceError> {
+        (**self).insert(user)
+    }
+    fn remove(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        (**self).remove(id)
+    }
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+}
+
+impl<R: UserRepo + ?Sized> UserRepo for Box<R> {
+    fn get(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        (**self).get(id)
+    }
+    fn insert(&self, user: NewUser) -> Result<UserEntity, ServiceError> {
+        (**self).insert(user)
+    }
+    fn remove(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        (**self).remove(id)
+    }
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+}
+
+impl<R: UserRepo + ?Sized> UserRepo for Arc<R> {
+    fn get(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        (**self).get(id)
+    }
+    fn insert(&self, user: NewUser) -> Result<UserEntity, ServiceError> {
+        (**self).insert(user)
+    }
+    fn remove(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        (**self).remove(id)
+    }
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+}
+
+// -----------------------------
+// In memory
+// -----------------------------
+
+#[derive(Debug, Default)]
+pub struct InMemoryUserRepo {
+    users: RwLock<HashMap<u64, UserEntity>>,
+    next_id: AtomicU64,
+}
+
+impl InMemoryUserRepo {
+    pub fn new() -> InMemoryUserRepo {
+        InMemoryUserRepo::default()
+    }
+}
+
+impl UserRepo for InMemoryUserRepo {
+    fn get(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        let users = self.users.read().unwrap();
+        users.get(&id.0).cloned().ok_or(ServiceError::NotFound(id))
+    }
+
+    fn insert(&self, user: NewUser) -> Result<UserEntity, ServiceError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let entity = user.into_entity(id, id);
+        self.users.write().unwrap().insert(id, entity.clone());
+        Ok(entity)
+    }
+
+    fn remove(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        let mut users = self.users.write().unwrap();
+        users.remove(&id.0).ok_or(ServiceError::NotFound(id))
+    }
+
+    fn len(&self) -> usize {
+        self.users.read().unwrap().len()
+    }
+}
+
+// -----------------------------
+// File backed
+// -----------------------------
+
+/// Append-only file with one tab-separated record per line and an
+/// in-memory index of record offsets.
+///
+/// Record layout: `id \t created_at \t active \t email \t name \t tag,tag`.
+/// A removal appends the tombstone `id \t -`. Every `get` seeks and reads
+/// its record back from the file.
+#[derive(Debug)]
+pub struct FileUserRepo {
+    file: Mutex<File>,
+    /// id -> (offset, length without the newline)
+    index: RwLock<HashMap<u64, (u64, usize)>>,
+    next_id: AtomicU64,
+}
+
+impl FileUserRepo {
+    /// Opens or creates the store at `path`, indexing existing records.
+    pub fn open(path: &Path) -> Result<FileUserRepo, ServiceError> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut index = HashMap::new();
+        let mut next_id = 0;
+        let mut offset = 0u64;
+        let mut reader = BufReader::new(&mut file);
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 0 {
+            let record = line.strip_suffix('\n').ok_or_else(|| {
+                ServiceError::Corrupt(format!("truncated record at byte {offset}"))
+            })?;
+            if let Some(id) = tombstone(record) {
+                index.remove(&id);
+            } else {
+                let entity = decode(record)?;
+                index.insert(entity.id, (offset, record.len()));
+                next_id = next_id.max(entity.id + 1);
+            }
+            offset += line.len() as u64;
+            line.clear();
+        }
+
+        Ok(FileUserRepo {
+            file: Mutex::new(file),
+            index: RwLock::new(index),
+            next_id: AtomicU64::new(next_id),
+        })
+    }
+
+    /// Writes `record` at the end of the file and returns its offset.
+    fn append(&self, record: &str) -> Result<u64, ServiceError> {
+        let mut file = self.file.lock().unwrap();
+        let offset = file.seek(SeekFrom::End(0))?;
+        file.write_all(record.as_bytes())?;
+        Ok(offset)
+    }
+}
+
+impl UserRepo for FileUserRepo {
+    fn get(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        let (offset, len) = *self
+            .index
+            .read()
+            .unwrap()
+            .get(&id.0)
+            .ok_or(ServiceError::NotFound(id))?;
+        let mut buf = vec![0; len];
+        {
+            let mut file = self.file.lock().unwrap();
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut buf)?;
+        }
+        let record = String::from_utf8(buf)
+            .map_err(|_| ServiceError::Corrupt(format!("user {} is not UTF-8", id.0)))?;
+        decode(&record)
+    }
+
+    fn insert(&self, user: NewUser) -> Result<UserEntity, ServiceError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let entity = user.into_entity(id, id);
+        let record = encode(&entity)?;
+        let offset = self.append(&record)?;
+        self.index
+            .write()
+            .unwrap()
+            .insert(id, (offset, record.len() - 1));
+        Ok(entity)
+    }
+
+    fn remove(&self, id: UserId) -> Result<UserEntity, ServiceError> {
+        let entity = self.get(id)?;
+        self.append(&format!("{}\t-\n", id.0))?;
+        self.index.write().unwrap().remove(&id.0);
+        Ok(entity)
+    }
+
+    fn len(&self) -> usize {
+        self.index.read().unwrap().len()
+    }
+}
+
+fn encode(e: &UserEntity) -> Result<String, ServiceError> {
+    let text_fields = [e.email.as_str(), e.display_name.as_str()];
+    if let Some(bad) = text_fields
+        .into_iter()
+        .chain(e.tags.iter().map(String::as_str))
+        .find(|s| s.contains(['\t', '\n']))
+    {
+        return Err(ServiceError::Invalid(format!(
+            "`{bad}` contains a tab or newline"
+        )));
+    }
+    if let Some(bad) = e.tags.iter().find(|t| t.is_empty() || t.contains(',')) {
+        return Err(ServiceError::Invalid(format!(
+            "tag `{bad}` is empty or contains a comma"
+        )));
+    }
+    Ok(format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\n",
+        e.id,
+        e.created_at,
+        u8::from(e.active),
+        e.email,
+        e.display_name,
+        e.tags.join(",")
+    ))
+}
+
+fn tombstone(record: &str) -> Option<u64> {
+    match record.split_once('\t') {
+        Some((id, "-")) => id.parse().ok(),
+        _ => None,
+    }
+}
+
+fn decode(record: &str) -> Result<UserEntity, ServiceError> {
+    let corrupt = || ServiceError::Corrupt(format!("bad record `{record}`"));
+    let mut fields = record.split('\t');
+    let mut next = || fields.next().ok_or_else(corrupt);
+
+    let id = next()?.parse().map_err(|_| corrupt())?;
+    let created_at = next()?.parse().map_err(|_| corrupt())?;
+    let active = match next()? {
+        "0" => false,
+        "1" => true,
+        _ => return Err(corrupt()),
+    };
+    let email = next()?.to_string();
+    let display_name = next()?.to_string();
+    let tags = match next()? {
+        "" => Vec::new(),
+        tags => tags.split(',').map(str::to_string).collect(),
+    };
+
+    Ok(UserEntity {
+        id,
+        email,
+        display_name,
+        tags,
+        active,
+        created_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::model::Email;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process;
+
+    /// A store path unique to this process and test, removed up front.
+    fn store(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rust_examples_{}_{name}.tsv", process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn new_user(name: &str, tags: &[&str]) -> NewUser {
+        NewUser {
+            email: Email::parse(format!("{name}@example.com")).unwrap(),
+            name: name.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn records_survive_a_reopen() {
+        let path = store("reopen");
+        let repo = FileUserRepo::open(&path).unwrap();
+        let ada = repo.insert(new_user("ada", &["admin", "ops"])).unwrap();
+        let bob = repo.insert(new_user("bob", &[])).unwrap();
+        drop(repo);
+
+        let repo = FileUserRepo::open(&path).unwrap();
+        assert_eq!(repo.len(), 2);
+        assert_eq!(repo.get(UserId(ada.id)).unwrap(), ada);
+        assert_eq!(repo.get(UserId(bob.id)).unwrap(), bob);
+        assert_eq!(repo.insert(new_user("cy", &[])).unwrap().id, 2);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tombstones_hide_removed_records() {
+        let path = store("tombstone");
+        let repo = FileUserRepo::open(&path).unwrap();
+        let ada = repo.insert(new_user("ada", &[])).unwrap();
+        let bob = repo.insert(new_user("bob", &[])).unwrap();
+        assert_eq!(repo.remove(UserId(ada.id)).unwrap(), ada);
+        assert!(matches!(
+            repo.get(UserId(ada.id)),
+            Err(ServiceError::NotFound(_))
+        ));
+        drop(repo);
+
+        let repo = FileUserRepo::open(&path).unwrap();
+        assert_eq!(repo.len(), 1);
+        assert!(matches!(
+            repo.get(UserId(ada.id)),
+            Err(ServiceError::NotFound(_))
+        ));
+        assert_eq!(repo.get(UserId(bob.id)).unwrap(), bob);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tabs_and_commas_are_rejected_before_writing() {
+        let path = store("invalid");
+        let repo = FileUserRepo::open(&path).unwrap();
+        for user in [
+            new_user("a\tb", &[]),
+            new_user("ada", &["x\ty"]),
+            new_user("ada", &["x,y"]),
+            new_user("ada", &[""]),
+        ] {
+            assert!(matches!(repo.insert(user), Err(ServiceError::Invalid(_))));
+        }
+        assert!(repo.is_empty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn decode_rejects_malformed_records() {
+        assert_eq!(tombstone("7\t-"), Some(7));
+        assert_eq!(tombstone("7\t0\t1\ta@b\tA\t"), None);
+        assert!(decode("7\t0\t1\ta@b\tA\t").unwrap().tags.is_empty());
+        for bad in ["7\t0\t2\ta@b\tA\t", "7\t0\t1\ta@b", "x\t0\t1\ta@b\tA\t"] {
+            assert!(matches!(decode(bad), Err(ServiceError::Corrupt(_))));
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::ServiceError;
+use super::model::{NewUser, User, UserId};
+use super::repo::UserRepo;
+
+/// Business rules over a repo held as `R`: by value, `&R`, `Arc<R>` or
+/// `Box<dyn UserRepo>` depending on the wiring.
+#[derive(Clone, Debug)]
+pub struct UserUseCase<R> {
+    repo: R,
+}
+
+impl<R: UserRepo> UserUseCase<R> {
+    pub fn new(repo: R) -> UserUseCase<R> {
+        UserUseCase { repo }
+    }
+
+    pub fn repo(&self) -> &R {
+        &self.repo
+    }
+
+    /// Maps the stored entity into the domain by moving its fields.
+    pub fn get_user(&self, id: UserId) -> Result<User, ServiceError> {
+        self.repo.get(id).map(User::from)
+    }
+
+    /// Maps through a reference, cloning every field.
+    pub fn get_user_cloned(&self, id: UserId) -> Result<User, ServiceError> {
+        let entity = self.repo.get(id)?;
+        Ok(User::from(&entity))
+    }
+
+    /// Rejects duplicate tags, then stores the user.
+    pub fn create_user(&self, user: NewUser) -> Result<User, ServiceError> {
+        check_tags(&user.tags)?;
+        self.repo.insert(user).map(User::from)
+    }
+
+    /// Keeps its own copy of the request and of the stored entity.
+    pub fn create_user_cloned(&self, user: &NewUser) -> Result<User, ServiceError> {
+        check_tags(&user.tags)?;
+        let entity = self.repo.insert(user.clone())?;
+        Ok(User::from(&entity))
+    }
+
+    pub fn delete_user(&self, id: UserId) -> Result<User, ServiceError> {
+        self.repo.remove(id).map(User::from)
+    }
+}
+
+fn check_tags(tags: &[String]) -> Result<(), ServiceError> {
+    for (i, tag) in tags.iter().enumerate() {
+        if tags[..i].contains(tag) {
+            return Err(ServiceError::Invalid(format!("duplicate tag `{tag}`")));
+        }
+    }
+    Ok(())
+}
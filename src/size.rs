@@ -0,0 +1,79 @@
+//! Binary-size and inlining footprint of a bench target per panic strategy.
+//!
+//! Builds a target once per [`Strategy`] through [`asm::emit_profile`] and
+//! compares the linked binary and the target's own functions. Unwinding
+//! needs landing pads that run destructors, which costs code size and can
+//! stop LLVM from inlining; `panic = "abort"` removes both.
+
+use crate::asm::{self, Format};
+use std::fs;
+use std::path::Path;
+
+/// A cargo profile plus the matching rustc flag, which cargo does not pass
+/// for bench targets on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Strategy {
+    pub panic: &'static str,
+    pub profile: &'static str,
+}
+
+pub const STRATEGIES: &[Strategy] = &[
+    Strategy {
+        panic: "unwind",
+        profile: "bench-unwind",
+    },
+    Strategy {
+        panic: "abort",
+        profile: "bench-abort",
+    },
+];
+
+/// What one build of a target weighs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Footprint {
+    /// Size of the linked bench binary, Criterion and std included.
+    pub binary_bytes: u64,
+    /// Out-of-line functions defined in the target; fewer means more inlining.
+    pub functions: usize,
+    pub instructions: usize,
+    /// Target functions with a personality routine, i.e. landing pads.
+    pub with_landing_pads: usize,
+}
+
+/// Builds `target` under `strategy` and measures it.
+pub fn measure(manifest_dir: &Path, target: &str, strategy: Strategy) -> Result<Footprint, String> {
+    let flag = format!("panic={}", strategy.panic);
+    let path = asm::emit_profile(
+        manifest_dir,
+        target,
+        Format::Asm,
+        strategy.profile,
+        &["-C", &flag],
+    )?;
+    let binary = path.with_extension("");
+    let binary_bytes = fs::metadata(&binary)
+        .map_err(|e| format!("cannot stat {}: {e}", binary.display()))?
+        .len();
+    let text =
+        fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+
+    let prefix = format!("{target}::");
+    let mut footprint = Footprint {
+        binary_bytes,
+        ..Footprint::default()
+    };
+    for f in asm::parse(&text, Format::Asm)
+        .iter()
+        .filter(|f| f.name.starts_with(&prefix))
+    {
+        footprint.functions += 1;
+        footprint.instructions += asm::stats(f).instructions;
+        if f.body
+            .iter()
+            .any(|l| l.trim().starts_with(".cfi_personality"))
+        {
+            footprint.with_landing_pads += 1;
+        }
+    }
+    Ok(footprint)
+}
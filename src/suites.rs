@@ -0,0 +1,162 @@
+//! Registry of bench targets grouped into named suites.
+//!
+//! Every `[[bench]]` in `Cargo.toml` has exactly one entry here. The
+//! `run-suite` binary uses this table to turn "all ownership benches" into
+//! the matching `cargo bench --bench …` invocation.
+
+use std::fmt;
+
+/// A named group of related bench targets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Suite {
+    /// `Vec`/`SmallVec`/`TinyVec` and map construction, growth and lookup.
+    Containers,
+    /// Move vs. clone vs. reference vs. `Box` for payloads and arrays.
+    Ownership,
+    /// Static vs. dynamic dispatch and error-type costs.
+    Dispatch,
+    /// Hand-written loops vs. iterator adapters.
+    Iterators,
+    /// `String` vs. `&str` building.
+    Strings,
+    /// Sorting, searching, SIMD and other whole-slice algorithms.
+    Algorithms,
+}
+
+impl Suite {
+    pub const ALL: [Suite; 6] = [
+        Suite::Containers,
+        Suite::Ownership,
+        Suite::Dispatch,
+        Suite::Iterators,
+        Suite::Strings,
+        Suite::Algorithms,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Suite::Containers => "containers",
+            Suite::Ownership => "ownership",
+            Suite::Dispatch => "dispatch",
+            Suite::Iterators => "iterators",
+            Suite::Strings => "strings",
+            Suite::Algorithms => "algorithms",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Suite> {
+        Suite::ALL.into_iter().find(|s| s.name() == name)
+    }
+
+    /// Bench targets registered under this suite, in declaration order.
+    pub fn benches(self) -> impl Iterator<Item = &'static Bench> {
+        BENCHES.iter().filter(move |b| b.suite == self)
+    }
+}
+
+impl fmt::Display for Suite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Slowdown, in percent of the baseline mean, that `run-suite compare`
+/// tolerates unless a target sets its own.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// One `[[bench]]` target.
+#[derive(Debug)]
+pub struct Bench {
+    /// Target name, as passed to `cargo bench --bench`.
+    pub name: &'static str,
+    pub suite: Suite,
+    /// Regression threshold in percent; see [`DEFAULT_THRESHOLD`].
+    pub threshold: f64,
+}
+
+impl Bench {
+    const fn new(name: &'static str, suite: Suite) -> Bench {
+        Bench {
+            name,
+            suite,
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+
+    const fn threshold(self, threshold: f64) -> Bench {
+        Bench { threshold, ..self }
+    }
+}
+
+pub const BENCHES: &[Bench] = &[
+    // containers
+    Bench::new("vector_allocations", Suite::Containers),
+    Bench::new("vectors_allocations", Suite::Containers),
+    Bench::new("vectors_fight", Suite::Containers),
+    // Allocation-heavy, so the allocator adds noise.
+    Bench::new("vectors_fight_2", Suite::Containers).threshold(15.0),
+    Bench::new("buffer_reuse", Suite::Containers).threshold(15.0),
+    Bench::new("maps_fight", Suite::Containers),
+    Bench::new("map_workloads", Suite::Containers),
+    Bench::new("vector_map", Suite::Containers),
+    // ownership
+    Bench::new("array", Suite::Ownership),
+    Bench::new("clone_move_reference", Suite::Ownership),
+    Bench::new("constructor", Suite::Ownership),
+    Bench::new("integer", Suite::Ownership),
+    Bench::new("layers_move", Suite::Ownership),
+    Bench::new("mapping", Suite::Ownership),
+    Bench::new("mapping_performance_cost", Suite::Ownership),
+    Bench::new("move_reference_pass", Suite::Ownership),
+    Bench::new("move_reference_return", Suite::Ownership),
+    Bench::new("service_layers", Suite::Ownership),
+    Bench::new("struct_size_sweep", Suite::Ownership),
+    // dispatch
+    // Tight, allocation-free loops; small slowdowns are real.
+    Bench::new("trait_direct", Suite::Dispatch).threshold(5.0),
+    Bench::new("async_dispatch", Suite::Dispatch),
+    Bench::new("error_paths", Suite::Dispatch),
+    Bench::new("panic_unwind", Suite::Dispatch),
+    // iterators
+    Bench::new("loop_iterator", Suite::Iterators),
+    Bench::new("iterator_slices", Suite::Iterators),
+    // strings
+    Bench::new("string_str", Suite::Strings),
+    Bench::new("string_building", Suite::Strings),
+    // algorithms
+    Bench::new("sorting", Suite::Algorithms),
+    Bench::new("simd", Suite::Algorithms),
+    Bench::new("float_reductions", Suite::Algorithms),
+];
+
+/// Looks up a bench target by its exact name.
+pub fn bench(name: &str) -> Option<&'static Bench> {
+    BENCHES.iter().find(|b| b.name == name)
+}
+
+/// Resolves suite and target names into a de-duplicated list of targets.
+///
+/// An empty selection means every registered target. Unknown names are
+/// returned as the error so the caller can report them.
+pub fn select(names: &[String]) -> Result<Vec<&'static Bench>, String> {
+    if names.is_empty() {
+        return Ok(BENCHES.iter().collect());
+    }
+
+    let mut out: Vec<&'static Bench> = Vec::new();
+    for name in names {
+        let found: Vec<&'static Bench> = match Suite::from_name(name) {
+            Some(suite) => suite.benches().collect(),
+            None => bench(name).into_iter().collect(),
+        };
+        if found.is_empty() {
+            return Err(name.clone());
+        }
+        for b in found {
+            if !out.iter().any(|o| o.name == b.name) {
+                out.push(b);
+            }
+        }
+    }
+    Ok(out)
+}
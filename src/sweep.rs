@@ -0,0 +1,33 @@
+//! Payload structs with a configurable number of fields.
+//!
+//! `build.rs` generates `PodN` and `MixedN` for every N in `SWEEP_FIELDS`,
+//! so the ownership benches can sweep struct size instead of comparing the
+//! three hand-written [`fixtures`](crate::fixtures) shapes. Both implement
+//! [`Fixture`]; a bench walks all of them with [`visit_all`].
+
+use crate::fixtures::{Fixture, text};
+
+/// Field makeup of a generated struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// Only `u64` fields; moves and clones are plain copies.
+    Pod,
+    /// `u64`s interleaved with `String`/`Vec<u8>`; clones allocate.
+    Mixed,
+}
+
+impl Layout {
+    pub fn name(self) -> &'static str {
+        match self {
+            Layout::Pod => "pod",
+            Layout::Mixed => "mixed",
+        }
+    }
+}
+
+/// Receives each generated type in turn; see [`visit_all`].
+pub trait Visitor {
+    fn visit<T: Fixture + 'static>(&mut self, layout: Layout);
+}
+
+include!(concat!(env!("OUT_DIR"), "/sweep.rs"));
@@ -0,0 +1,129 @@
+//! Which kernels of a bench target LLVM vectorised, per CPU target.
+//!
+//! A timing alone does not say whether a loop was vectorised. [`inspect`]
+//! builds a target through [`asm::emit_profile`] once per [`Build`] and
+//! counts packed SIMD arithmetic ([`asm::Stats::packed_ops`]) in every
+//! function of the target's `kernels` module. `run-suite report` appends
+//! the result to the findings pages of the targets in [`TARGETS`].
+
+use crate::asm::{self, Format};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// A cargo profile plus the `-C target-cpu` it is meant to run with, which
+/// a profile cannot set on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Build {
+    /// Suffix of the bench ids this build produces.
+    pub name: &'static str,
+    pub profile: &'static str,
+    pub target_cpu: Option<&'static str>,
+}
+
+pub const BASE: Build = Build {
+    name: "base",
+    profile: "bench",
+    target_cpu: None,
+};
+
+/// What `run-suite run --native` builds.
+pub const NATIVE: Build = Build {
+    name: "native",
+    profile: "bench-native",
+    target_cpu: Some("native"),
+};
+
+pub const BUILDS: &[Build] = &[BASE, NATIVE];
+
+/// Targets whose findings pages get a vectorisation table.
+pub const TARGETS: &[&str] = &["simd"];
+
+/// One kernel function and its packed SIMD operations under a build.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Kernel {
+    /// Path inside the `kernels` module, e.g. `sum_u32_scalar`.
+    pub name: String,
+    pub packed_ops: usize,
+}
+
+impl Kernel {
+    pub fn vectorised(&self) -> bool {
+        self.packed_ops > 0
+    }
+}
+
+/// Builds `target` as `build` and inspects its `kernels` module.
+pub fn inspect(manifest_dir: &Path, target: &str, build: Build) -> Result<Vec<Kernel>, String> {
+    let flag = build.target_cpu.map(|cpu| format!("target-cpu={cpu}"));
+    let args: Vec<&str> = match &flag {
+        Some(flag) => vec!["-C", flag],
+        None => Vec::new(),
+    };
+    let path = asm::emit_profile(manifest_dir, target, Format::Asm, build.profile, &args)?;
+    let text =
+        fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+
+    let prefix = format!("{target}::kernels::");
+    Ok(asm::parse(&text, Format::Asm)
+        .iter()
+        .filter_map(|f| {
+            let name = f.name.strip_prefix(&prefix)?;
+            Some(Kernel {
+                // Intrinsic kernels live in a per-architecture submodule.
+                name: name.rsplit("::").next().unwrap_or(name).to_string(),
+                packed_ops: asm::stats(f).packed_ops,
+            })
+        })
+        .collect())
+}
+
+/// A markdown section with one row per kernel and one column per build.
+pub fn to_markdown(builds: &[(Build, Vec<Kernel>)]) -> String {
+    let mut md = String::from("\n## Vectorisation\n\n");
+    let _ = writeln!(
+        md,
+        "Packed SIMD add/sub/mul/FMA instructions in each kernel's assembly; \
+         `scalar` means LLVM did not vectorise it.\n"
+    );
+    let _ = write!(md, "| kernel |");
+    for (build, _) in builds {
+        let _ = write!(md, " `{}` |", build.name);
+    }
+    let _ = write!(md, "\n|---|");
+    for _ in builds {
+        let _ = write!(md, "---|");
+    }
+    md.push('\n');
+
+    let mut names: Vec<&str> = Vec::new();
+    for (_, kernels) in builds {
+        for k in kernels {
+            if !names.contains(&k.name.as_str()) {
+                names.push(&k.name);
+            }
+        }
+    }
+    for name in names {
+        let _ = write!(md, "| `{name}` |");
+        for (_, kernels) in builds {
+            let text = match kernels.iter().find(|k| k.name == name) {
+                None => "inlined".to_string(),
+                Some(k) if k.vectorised() => format!("vectorised ({})", k.packed_ops),
+                Some(_) => "scalar".to_string(),
+            };
+            let _ = write!(md, " {text} |");
+        }
+        md.push('\n');
+    }
+    md
+}
+
+/// [`inspect`] under every [`Build`], rendered with [`to_markdown`].
+pub fn report(manifest_dir: &Path, target: &str) -> Result<String, String> {
+    let mut builds = Vec::new();
+    for build in BUILDS {
+        builds.push((*build, inspect(manifest_dir, target, *build)?));
+    }
+    Ok(to_markdown(&builds))
+}